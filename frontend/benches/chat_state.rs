@@ -0,0 +1,316 @@
+//! Applies events to the chat state of a large synthetic account and
+//! prints how long each kind of operation takes, then compares the index of
+//! the rooms with the plain list it replaced on the same number of rooms.
+//!
+//! Run with `cargo bench -p frontend`.
+
+use chrono::{Duration, Utc};
+use frontend::states::{index::OrderedMap, store::Store, websocket::WebSocketState};
+use shared::{
+    event::ServerEvent,
+    friend::{AcceptFriendResponse, FriendInfo},
+    member::MemberInfo,
+    message::{InitialResponse, MessageInfo, NewMessageResponse},
+    room::RoomInfo,
+};
+use std::{
+    hint::black_box,
+    time::{Duration as StdDuration, Instant},
+};
+
+const CURR_USER: i64 = 1;
+const ROOMS: i64 = 5_000;
+const FRIENDS: i64 = 5_000;
+const MEMBERS: i64 = 8;
+const MESSAGES: i64 = 20;
+const EVENTS: usize = 20_000;
+
+// ========================// Data //======================== //
+
+/// Small xorshift generator, so that every run picks the same rooms
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, max: i64) -> i64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % max as u64) as i64
+    }
+}
+
+fn friend(id: i64, status: &str) -> FriendInfo {
+    FriendInfo {
+        id,
+        username: format!("user{}", id),
+        nickname: format!("User {}", id),
+        avatar: String::new(),
+        bio: String::new(),
+        status: status.to_owned(),
+        room_id: id,
+        first: false,
+        create_at: Utc::now(),
+    }
+}
+
+fn message(id: i64, sid: i64) -> MessageInfo {
+    MessageInfo {
+        id,
+        sid,
+        name: format!("User {}", sid),
+        avatar: String::new(),
+        content: "Lorem ipsum dolor sit amet".to_owned(),
+        kind: "text".to_owned(),
+        send_at: Utc::now() - Duration::seconds(id),
+    }
+}
+
+fn initial_response() -> InitialResponse {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+
+    let rooms = (1..=ROOMS)
+        .map(|id| RoomInfo {
+            id,
+            name: format!("Room {}", id),
+            cover: String::new(),
+            category: "public".to_owned(),
+            create_at: Utc::now(),
+            members: (0..MEMBERS)
+                .map(|i| MemberInfo {
+                    id: if i == 0 {
+                        CURR_USER
+                    } else {
+                        2 + rng.next(FRIENDS * 2)
+                    },
+                    name: String::new(),
+                    avatar: String::new(),
+                    rank: "member".to_owned(),
+                    join_at: Utc::now(),
+                })
+                .collect(),
+            messages: (0..MESSAGES)
+                .map(|i| message(id * MESSAGES + i, 2 + rng.next(FRIENDS)))
+                .collect(),
+        })
+        .collect();
+
+    let friends = (2..2 + FRIENDS).map(|id| friend(id, "accepted")).collect();
+
+    InitialResponse { rooms, friends }
+}
+
+// ========================// Baseline //======================== //
+
+/// Items in a list scanned for every id, the way the rooms were kept before
+/// `OrderedMap`, the most recent last
+#[derive(Default)]
+struct LinearMap<T>(Vec<(i64, T)>);
+
+impl<T> LinearMap<T> {
+    fn get_mut(&mut self, id: i64) -> Option<&mut T> {
+        self.0
+            .iter_mut()
+            .find(|(x, _)| *x == id)
+            .map(|(_, item)| item)
+    }
+
+    fn insert(&mut self, id: i64, item: T) {
+        self.remove(id);
+        self.0.push((id, item));
+    }
+
+    fn touch(&mut self, id: i64) -> bool {
+        match self.0.iter().position(|(x, _)| *x == id) {
+            Some(idx) => {
+                let item = self.0.remove(idx);
+                self.0.push(item);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove(&mut self, id: i64) -> Option<T> {
+        let idx = self.0.iter().position(|(x, _)| *x == id)?;
+        Some(self.0.remove(idx).1)
+    }
+
+    fn values(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.0.iter().map(|(_, item)| item)
+    }
+}
+
+/// Time the same operations on both the list and the index, with as many
+/// rooms as the synthetic account
+fn compare_baseline(rng: &mut Rng) {
+    let mut list = LinearMap::default();
+    let mut index = OrderedMap::default();
+    for id in 1..=ROOMS {
+        list.insert(id, 0_u64);
+        index.insert(id, 0_u64);
+    }
+    let ids: Vec<i64> = (0..EVENTS).map(|_| 1 + rng.next(ROOMS)).collect();
+
+    println!();
+    println!(
+        "{:<28} {:>8} {:>14} {:>14} {:>8}",
+        "baseline, same rooms", "ops", "Vec ns/op", "index ns/op", "speedup"
+    );
+
+    // a message in a room: update it and move it to the top
+    let vec = time(|| {
+        for &id in ids.iter() {
+            *list.get_mut(id).unwrap() += 1;
+            list.touch(id);
+        }
+    });
+    let ordered = time(|| {
+        for &id in ids.iter() {
+            *index.get_mut(id).unwrap() += 1;
+            index.touch(id);
+        }
+    });
+    compare("receive message", EVENTS, vec, ordered);
+
+    // an update of a room that keeps its place
+    let vec = time(|| {
+        for &id in ids.iter() {
+            black_box(list.get_mut(id));
+        }
+    });
+    let ordered = time(|| {
+        for &id in ids.iter() {
+            black_box(index.get_mut(id));
+        }
+    });
+    compare("lookup by id", EVENTS, vec, ordered);
+
+    // a room left and joined again
+    let vec = time(|| {
+        for &id in ids.iter() {
+            let item = list.remove(id).unwrap();
+            list.insert(id, item);
+        }
+    });
+    let ordered = time(|| {
+        for &id in ids.iter() {
+            let item = index.remove(id).unwrap();
+            index.insert(id, item);
+        }
+    });
+    compare("leave and join", EVENTS, vec, ordered);
+
+    let vec = time(|| {
+        for _ in 0..EVENTS {
+            black_box(list.values().rev().take(50).count());
+        }
+    });
+    let ordered = time(|| {
+        for _ in 0..EVENTS {
+            black_box(index.values().rev().take(50).count());
+        }
+    });
+    compare("latest 50 rooms", EVENTS, vec, ordered);
+}
+
+// ========================// Bench //======================== //
+
+fn time(f: impl FnOnce()) -> StdDuration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
+
+fn compare(name: &str, count: usize, vec: StdDuration, ordered: StdDuration) {
+    let per_op = |elapsed: StdDuration| elapsed.as_nanos() as f64 / count as f64;
+    println!(
+        "{:<28} {:>8} {:>14.0} {:>14.0} {:>7.2}x",
+        name,
+        count,
+        per_op(vec),
+        per_op(ordered),
+        vec.as_secs_f64() / ordered.as_secs_f64().max(f64::EPSILON)
+    );
+}
+
+fn report(name: &str, count: usize, elapsed: StdDuration) {
+    let per_op = elapsed.as_nanos() as f64 / count as f64;
+    println!(
+        "{:<28} {:>8} ops {:>10.2} ms {:>10.0} ns/op",
+        name,
+        count,
+        elapsed.as_secs_f64() * 1_000.0,
+        per_op
+    );
+}
+
+fn main() {
+    let store = Store::default();
+    let mut state = WebSocketState::new(CURR_USER);
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+
+    println!("{} rooms, {} friends", ROOMS, FRIENDS);
+
+    let resp = initial_response();
+    let start = Instant::now();
+    state
+        .handle_event(ServerEvent::Initialized(resp), &store)
+        .ok();
+    report("initialized", 1, start.elapsed());
+
+    let events: Vec<ServerEvent> = (0..EVENTS)
+        .map(|i| {
+            let room_id = 1 + rng.next(ROOMS);
+            ServerEvent::ReceiveMessage(NewMessageResponse {
+                room_id,
+                message: message(ROOMS * MESSAGES + i as i64, 2 + rng.next(FRIENDS)),
+                nonce: None,
+            })
+        })
+        .collect();
+    let start = Instant::now();
+    for event in events {
+        state.handle_event(event, &store).ok();
+    }
+    report("receive message", EVENTS, start.elapsed());
+
+    let rooms: Vec<i64> = (0..EVENTS).map(|_| 1 + rng.next(ROOMS)).collect();
+    let start = Instant::now();
+    for room_id in rooms.iter() {
+        state.member_items(*room_id);
+    }
+    report("members, first time", EVENTS, start.elapsed());
+
+    let start = Instant::now();
+    for room_id in rooms.iter() {
+        state.member_items(*room_id);
+    }
+    report("members, cached", EVENTS, start.elapsed());
+
+    let start = Instant::now();
+    for room_id in rooms.iter() {
+        state.message_items(*room_id);
+    }
+    report("message items", EVENTS, start.elapsed());
+
+    let start = Instant::now();
+    let mut count = 0;
+    for _ in 0..100 {
+        count += state.rooms.values().rev().take(50).count();
+    }
+    report("latest 50 rooms", count, start.elapsed());
+
+    let events: Vec<ServerEvent> = (0..1_000)
+        .map(|_| {
+            let friend = friend(2 + FRIENDS + rng.next(FRIENDS), "accepted");
+            ServerEvent::AcceptedFriend(AcceptFriendResponse { friend })
+        })
+        .collect();
+    let start = Instant::now();
+    for event in events {
+        state.handle_event(event, &store).ok();
+    }
+    report("accepted friend", 1_000, start.elapsed());
+
+    compare_baseline(&mut rng);
+}
@@ -0,0 +1,194 @@
+# English, the fallback of every other locale
+
+## Common
+
+cancel = Cancel
+submit = Submit
+save = Save
+done = Done
+delete = Delete
+leave = Leave
+chat = Chat
+username = Username
+password = Password
+back = Back
+copied = Copied to clipboard
+
+## Dates
+
+today = Today
+yesterday = Yesterday
+date-day = { $weekday }, { $day } { $month }
+date-day-year = { $day } { $month }, { $year }
+date-numeric = { $year }-{ $month }-{ $day }
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
+weekday-1 = Monday
+weekday-2 = Tuesday
+weekday-3 = Wednesday
+weekday-4 = Thursday
+weekday-5 = Friday
+weekday-6 = Saturday
+weekday-7 = Sunday
+
+## Login and register
+
+login-title = Log In
+login-processing = Processing ...
+login-remember = Remember me
+login-forget = Forget password?
+login-no-account = Don't have an account?
+login-register = Register here.
+register-title = Register
+register-code = Invitation code
+register-submitting = Submitting ...
+register-has-account = Already have an account?
+register-login = Login here.
+register-done = Registration successfully, { $name }
+not-found = Not Found
+
+## Chat
+
+conn-reconnecting = Reconnecting…
+conn-offline = You are offline
+rooms-search = Search rooms...
+rooms-title = CHAT ROOMS
+room-draft = Draft:
+room-blank = Blank
+room-mute = Mute
+room-unmute = Unmute
+room-sound = Sound
+room-sound-default = Default ({ $pack })
+new-room = New room
+new-room-name = Name:
+new-room-no-friends = You have no friends to create a room!
+members-search = Search members...
+members-title = MEMBERS
+switcher-placeholder = Jump to a room or a friend...
+switcher-nothing = Nothing matches
+switcher-keys = ↑↓ to move, Enter to open, Esc to close
+switcher-friend = Friend @{ $name }
+switcher-member = Member { $name }
+
+## Messages
+
+unread-divider = Unread messages
+new-messages = New messages ({ $count }) ↓
+message-sending = Sending...
+message-waiting = Waiting for connection
+message-failed = Failed to send
+message-retry = Retry
+message-discard = Discard
+message-placeholder = Type your message here...
+message-subject = The message
+name-subject = The name
+field-invalid = { $subject } is not valid
+field-length-between = { $subject } must be between { $min } and { $max } characters
+field-length-min = { $subject } must be at least { $min } characters
+field-length-max = { $subject } must be at most { $max } characters
+field-range-between = { $subject } must be between { $min } and { $max }
+field-range-min = { $subject } must be at least { $min }
+field-range-max = { $subject } must be at most { $max }
+
+## Commands
+
+command-unknown = Unknown command /{ $name }
+command-usage = Usage: { $usage }
+command-denied = You cannot use /{ $name } in this room
+command-no-friend = No friend named { $name } to invite
+command-no-member = No member named { $name } to remove
+command-rename = Rename the room
+command-invite = Add a friend to the room
+command-kick = Remove a member from the room
+command-leave = Leave the room
+command-me = Say what you are doing
+command-shrug = Append ¯\_(ツ)_/¯ to the message
+
+## Friends
+
+friends-search = Search friends...
+friends-title = MY FRIENDS
+friend-info = Information
+friend-nick = Nick:
+new-friend = New friend
+new-friend-incoming = Incoming
+new-friend-outgoing = Outgoing
+new-friend-nothing = Nothing to find
+new-friend-find = Find new friend...
+
+## Account
+
+admin-console = Admin Console
+update-profile = Update Profile
+change-password = Change Password
+settings = Settings
+logout = Logout
+profile-nickname = Nickname
+profile-avatar = Avatar
+profile-bio = Bio
+profile-updated = Profile updated
+password-title = Change password
+password-current = Current password
+password-new = New password
+password-confirm = Confirm new password
+password-mismatch = The two new passwords do not match
+login-expired = Login expired
+login-expired-desc = Enter the password of { $name } to continue
+
+## Settings
+
+settings-language = Language
+settings-theme = Theme
+settings-background = Background
+theme-light = Light
+theme-dark = Dark
+theme-system = System
+theme-contrast = High contrast
+background-photo = Photo
+background-aurora = Aurora
+background-dusk = Dusk
+background-plain = Plain
+settings-notifications = Desktop notifications
+settings-notifications-desc = Show new messages while the page is in the background. Muted rooms stay silent.
+settings-notifications-blocked = Notifications are blocked by the browser
+settings-sounds = Sounds
+settings-sounds-desc = Played for new messages, mentions and friend requests. A room can pick its own.
+settings-volume = Volume
+sound-chime = Chime
+sound-pop = Pop
+sound-bell = Bell
+sound-off = Off
+
+## Admin
+
+users-title = USERS
+users-delete-selected = Delete selected
+users-user = User
+users-role = Role
+users-created = Created
+users-status = Status
+users-prev = Prev
+users-next = Next
+users-page = Page { $page } of { $pages }
+users-restore = Restore
+users-deleted = Deleted
+users-active = Active
+users-create = Create
+role-user = user
+role-admin = admin
+invite-title = INVITATION CODE
+invite-copy = Copy
+invite-expires = Expires { $date }
+invite-length = Length
+invite-days = Valid days
+invite-generate = Generate
@@ -0,0 +1,194 @@
+# 简体中文
+
+## Common
+
+cancel = 取消
+submit = 提交
+save = 保存
+done = 完成
+delete = 删除
+leave = 退出
+chat = 聊天
+username = 用户名
+password = 密码
+back = 返回
+copied = 已复制到剪贴板
+
+## Dates
+
+today = 今天
+yesterday = 昨天
+date-day = { $month }{ $day }日 { $weekday }
+date-day-year = { $year }年{ $month }{ $day }日
+date-numeric = { $year }/{ $month }/{ $day }
+month-1 = 1月
+month-2 = 2月
+month-3 = 3月
+month-4 = 4月
+month-5 = 5月
+month-6 = 6月
+month-7 = 7月
+month-8 = 8月
+month-9 = 9月
+month-10 = 10月
+month-11 = 11月
+month-12 = 12月
+weekday-1 = 星期一
+weekday-2 = 星期二
+weekday-3 = 星期三
+weekday-4 = 星期四
+weekday-5 = 星期五
+weekday-6 = 星期六
+weekday-7 = 星期日
+
+## Login and register
+
+login-title = 登录
+login-processing = 登录中 ...
+login-remember = 记住我
+login-forget = 忘记密码？
+login-no-account = 还没有账号？
+login-register = 立即注册。
+register-title = 注册
+register-code = 邀请码
+register-submitting = 提交中 ...
+register-has-account = 已有账号？
+register-login = 立即登录。
+register-done = 注册成功，{ $name }
+not-found = 页面不存在
+
+## Chat
+
+conn-reconnecting = 正在重新连接…
+conn-offline = 你已离线
+rooms-search = 搜索聊天室...
+rooms-title = 聊天室
+room-draft = 草稿：
+room-blank = 空白
+room-mute = 免打扰
+room-unmute = 取消免打扰
+room-sound = 提示音
+room-sound-default = 默认（{ $pack }）
+new-room = 新建聊天室
+new-room-name = 名称：
+new-room-no-friends = 你还没有好友，无法创建聊天室！
+members-search = 搜索成员...
+members-title = 成员
+switcher-placeholder = 跳转到聊天室或好友...
+switcher-nothing = 没有匹配的结果
+switcher-keys = ↑↓ 选择，Enter 打开，Esc 关闭
+switcher-friend = 好友 @{ $name }
+switcher-member = 成员 { $name }
+
+## Messages
+
+unread-divider = 未读消息
+new-messages = { $count } 条新消息 ↓
+message-sending = 发送中...
+message-waiting = 等待连接
+message-failed = 发送失败
+message-retry = 重试
+message-discard = 放弃
+message-placeholder = 在这里输入消息...
+message-subject = 消息
+name-subject = 名称
+field-invalid = { $subject }无效
+field-length-between = { $subject }须为 { $min } 到 { $max } 个字符
+field-length-min = { $subject }至少 { $min } 个字符
+field-length-max = { $subject }最多 { $max } 个字符
+field-range-between = { $subject }须在 { $min } 到 { $max } 之间
+field-range-min = { $subject }不能小于 { $min }
+field-range-max = { $subject }不能大于 { $max }
+
+## Commands
+
+command-unknown = 未知命令 /{ $name }
+command-usage = 用法：{ $usage }
+command-denied = 你不能在此聊天室使用 /{ $name }
+command-no-friend = 没有可邀请的好友 { $name }
+command-no-member = 没有可移除的成员 { $name }
+command-rename = 重命名聊天室
+command-invite = 邀请好友加入聊天室
+command-kick = 将成员移出聊天室
+command-leave = 退出聊天室
+command-me = 描述你正在做的事
+command-shrug = 在消息后加上 ¯\_(ツ)_/¯
+
+## Friends
+
+friends-search = 搜索好友...
+friends-title = 我的好友
+friend-info = 资料
+friend-nick = 昵称：
+new-friend = 添加好友
+new-friend-incoming = 收到的请求
+new-friend-outgoing = 发出的请求
+new-friend-nothing = 没有找到
+new-friend-find = 查找新好友...
+
+## Account
+
+admin-console = 管理后台
+update-profile = 修改资料
+change-password = 修改密码
+settings = 设置
+logout = 退出登录
+profile-nickname = 昵称
+profile-avatar = 头像
+profile-bio = 简介
+profile-updated = 资料已更新
+password-title = 修改密码
+password-current = 当前密码
+password-new = 新密码
+password-confirm = 确认新密码
+password-mismatch = 两次输入的新密码不一致
+login-expired = 登录已过期
+login-expired-desc = 输入 { $name } 的密码以继续
+
+## Settings
+
+settings-language = 语言
+settings-theme = 主题
+settings-background = 聊天背景
+theme-light = 浅色
+theme-dark = 深色
+theme-system = 跟随系统
+theme-contrast = 高对比度
+background-photo = 照片
+background-aurora = 极光
+background-dusk = 黄昏
+background-plain = 纯色
+settings-notifications = 桌面通知
+settings-notifications-desc = 页面在后台时显示新消息，免打扰的聊天室不会通知。
+settings-notifications-blocked = 通知已被浏览器阻止
+settings-sounds = 提示音
+settings-sounds-desc = 收到新消息、被提及和好友请求时播放，每个聊天室可单独设置。
+settings-volume = 音量
+sound-chime = 风铃
+sound-pop = 气泡
+sound-bell = 铃声
+sound-off = 关闭
+
+## Admin
+
+users-title = 用户
+users-delete-selected = 删除所选
+users-user = 用户
+users-role = 角色
+users-created = 创建时间
+users-status = 状态
+users-prev = 上一页
+users-next = 下一页
+users-page = 第 { $page } 页，共 { $pages } 页
+users-restore = 恢复
+users-deleted = 已删除
+users-active = 正常
+users-create = 创建
+role-user = 普通用户
+role-admin = 管理员
+invite-title = 邀请码
+invite-copy = 复制
+invite-expires = { $date } 过期
+invite-length = 长度
+invite-days = 有效天数
+invite-generate = 生成
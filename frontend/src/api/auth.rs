@@ -1,4 +1,5 @@
 use super::{config::ApiConfig, to_console, ApiError};
+use crate::config::api_url;
 use gloo_net::http::{Method, Request};
 use serde::{de::DeserializeOwned, Serialize};
 use web_sys::RequestCredentials;
@@ -15,7 +16,7 @@ impl AuthApi {
     pub fn new(api: ApiConfig) -> Self {
         let (url, method) = api.params();
         Self {
-            url: url.to_owned(),
+            url: api_url(url),
             method,
         }
     }
@@ -51,6 +52,8 @@ impl AuthApi {
             let msg = resp.text().await.map_err(to_console)?;
             match resp.status() {
                 400 => Err(ApiError::Toast(msg)),
+                // the refresh token is missing, refused or expired
+                401 | 402 => Err(ApiError::Expire),
                 _ => {
                     gloo_console::error!(msg);
                     Err(ApiError::Console)
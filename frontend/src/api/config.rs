@@ -35,4 +35,11 @@ api_config! {
     (RenewToken, "/api/auth/renew-token", POST);
     (Logout, "/api/auth/logout", POST);
     (GetUserByName, "/api/user/username", GET);
+    (UpdateProfile, "/api/user/profile", PATCH);
+    (ChangePassword, "/api/user/password", PATCH);
+    (ListUsers, "/api/user", GET);
+    (CreateUser, "/api/user", POST);
+    (UpdateUser, "/api/user", PATCH);
+    (DeleteUsers, "/api/user", DELETE);
+    (CreateInvitation, "/api/invitation", POST);
 }
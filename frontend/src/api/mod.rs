@@ -1,11 +1,12 @@
 pub mod auth;
 pub mod config;
 pub mod private;
+pub mod renew;
 
 // ========================// ApiError //======================== //
 
 /// Process of responding error from server
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum ApiError {
     // error message displayed to users
     Toast(String),
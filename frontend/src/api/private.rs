@@ -1,9 +1,8 @@
-use super::{auth::AuthApi, config::ApiConfig, to_console, ApiError};
-use crate::states::auth::{AuthAction, AuthState};
+use super::{config::ApiConfig, renew::renew_token, to_console, ApiError};
+use crate::{config::api_url, states::auth::AuthState};
 use gloo_net::http::{Method, Request};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
-use shared::auth::RenewTokenResponse;
 use web_sys::RequestCredentials;
 use yew::UseReducerHandle;
 
@@ -20,7 +19,7 @@ impl PrivateApi {
     pub fn new(api: ApiConfig, auth: UseReducerHandle<AuthState>) -> Self {
         let (url, method) = api.params();
         Self {
-            url: url.to_owned(),
+            url: api_url(url),
             method,
             auth,
         }
@@ -45,7 +44,7 @@ impl PrivateApi {
             match resp.status() {
                 400 => Err(ApiError::Toast(msg)),
                 402 => {
-                    let token = self.renew_token().await?;
+                    let token = renew_token(self.auth.clone()).await?;
                     self.resend(payload, token).await
                 }
                 _ => {
@@ -61,7 +60,7 @@ impl PrivateApi {
         T: Serialize,
     {
         let request = if self.method.to_string() == "GET" {
-            let query = match serde_json::to_value(&payload).map_err(to_console)? {
+            let query = match serde_json::to_value(payload).map_err(to_console)? {
                 Value::Object(req_map) => req_map
                     .into_iter()
                     .map(|(key, val)| format_map(key, val))
@@ -94,22 +93,6 @@ impl PrivateApi {
         Ok(request)
     }
 
-    async fn renew_token(&self) -> Result<String, ApiError> {
-        let api = AuthApi::new(ApiConfig::RenewToken);
-        match api.send::<(), RenewTokenResponse>(None).await {
-            Ok(data) => {
-                let token = data.access_token.clone();
-                self.auth.dispatch(AuthAction::Renew(data));
-                Ok(token)
-            }
-            Err(e) => {
-                // TODO: if expired, add login dialog here
-                self.auth.dispatch(AuthAction::Clear);
-                Err(e)
-            }
-        }
-    }
-
     async fn resend<T, D>(&self, payload: &T, token: String) -> Result<D, ApiError>
     where
         T: Serialize,
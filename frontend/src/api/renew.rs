@@ -0,0 +1,78 @@
+use super::{auth::AuthApi, config::ApiConfig, ApiError};
+use crate::states::auth::{AuthAction, AuthState};
+use futures::{
+    channel::oneshot,
+    future::{LocalBoxFuture, Shared},
+    FutureExt,
+};
+use shared::auth::RenewTokenResponse;
+use std::cell::RefCell;
+use yew::UseReducerHandle;
+
+type Renewal = Shared<LocalBoxFuture<'static, Result<String, ApiError>>>;
+
+thread_local! {
+    // the renewal in flight, shared by every request that needs a new token
+    static RENEWAL: RefCell<Option<Renewal>> = const { RefCell::new(None) };
+    // requests suspended until the user logs in again
+    static REAUTH_WAITERS: RefCell<Vec<oneshot::Sender<Option<String>>>> = const { RefCell::new(Vec::new()) };
+}
+
+// ========================// renew_token //======================== //
+
+/// Renew the access token, joining the renewal in flight if there is one
+pub async fn renew_token(auth: UseReducerHandle<AuthState>) -> Result<String, ApiError> {
+    let renewal = RENEWAL.with(|x| {
+        x.borrow_mut()
+            .get_or_insert_with(|| {
+                async move {
+                    let result = renew(auth).await;
+                    RENEWAL.with(|x| x.borrow_mut().take());
+                    result
+                }
+                .boxed_local()
+                .shared()
+            })
+            .clone()
+    });
+
+    renewal.await
+}
+
+async fn renew(auth: UseReducerHandle<AuthState>) -> Result<String, ApiError> {
+    let api = AuthApi::new(ApiConfig::RenewToken);
+    match api.send::<(), RenewTokenResponse>(None).await {
+        Ok(data) => {
+            let token = data.access_token.clone();
+            auth.dispatch(AuthAction::Renew(data));
+            Ok(token)
+        }
+        // the login is over, only logging in again helps
+        Err(ApiError::Expire) => relogin(auth).await,
+        // the server could not be reached, the caller may try again later
+        Err(e) => Err(e),
+    }
+}
+
+/// Ask the user to log in again and wait for the new access token
+pub async fn relogin(auth: UseReducerHandle<AuthState>) -> Result<String, ApiError> {
+    let (tx, rx) = oneshot::channel();
+    REAUTH_WAITERS.with(|x| x.borrow_mut().push(tx));
+    auth.dispatch(AuthAction::Expire);
+
+    match rx.await {
+        Ok(Some(token)) => Ok(token),
+        _ => Err(ApiError::Expire),
+    }
+}
+
+// ========================// Reauth //======================== //
+
+/// Resume the requests suspended by an expired login with the new access token,
+/// or fail them if the user gives up logging in again
+pub fn resume_requests(token: Option<String>) {
+    let waiters = REAUTH_WAITERS.with(|x| x.take());
+    for tx in waiters {
+        let _ = tx.send(token.clone());
+    }
+}
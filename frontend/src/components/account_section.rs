@@ -1,15 +1,30 @@
-use super::common::Avatar;
+use super::{
+    common::Avatar, password_modal::PasswordModal, profile_modal::ProfileModal,
+    settings_modal::SettingsModal,
+};
 use crate::{
-    hooks::{use_click_away, use_logout},
+    hooks::{use_click_away, use_lang, use_logout},
+    pages::route::Route,
     states::auth::AuthContext,
 };
 use yew::{function_component, html, use_context, use_node_ref, use_state, Callback, Html};
+use yew_router::prelude::use_navigator;
+
+#[derive(Clone, Copy, PartialEq)]
+enum AccountModal {
+    Closed,
+    Profile,
+    Password,
+    Settings,
+}
 
 // ========================// AccountSection //======================== //
 
 #[function_component]
 pub fn AccountSection() -> Html {
+    let lang = use_lang();
     let auth = use_context::<AuthContext>().unwrap();
+    let navigator = use_navigator().unwrap();
     let logout = use_logout();
 
     let open = use_state(|| false);
@@ -26,32 +41,77 @@ pub fn AccountSection() -> Html {
     };
     use_click_away(node_ref.clone(), onclose);
 
+    let modal = use_state(|| AccountModal::Closed);
+    let open_modal = {
+        let open = open.clone();
+        let modal = modal.clone();
+        move |target: AccountModal| {
+            let open = open.clone();
+            let modal = modal.clone();
+            move |_| {
+                open.set(false);
+                modal.set(target);
+            }
+        }
+    };
+    let onclose_modal = {
+        let modal = modal.clone();
+        Callback::from(move |_| modal.set(AccountModal::Closed))
+    };
+
+    let show_modal = match *modal {
+        AccountModal::Closed => html! {},
+        AccountModal::Profile => html! { <ProfileModal onclose={onclose_modal} /> },
+        AccountModal::Password => html! { <PasswordModal onclose={onclose_modal} /> },
+        AccountModal::Settings => html! { <SettingsModal onclose={onclose_modal} /> },
+    };
+
+    let show_admin = if auth.user.role == "admin" {
+        let onadmin = move |_| navigator.push(&Route::Admin);
+
+        html! {
+            <div class="py-1">
+                <div onclick={onadmin} class="px-4 py-3 md:py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-white">
+                    {lang.t("admin-console")}
+                </div>
+            </div>
+        }
+    } else {
+        html! {}
+    };
+
     let onlogout = move |_| logout();
 
     html! {
         <>
-        <div {onclick} class="rounded-full cursor-pointer hover:ring-2 hover:ring-slate-50">
+        <div {onclick} class="rounded-full cursor-pointer hover:ring-2 hover:ring-white">
             <Avatar image={auth.user.avatar.clone()} classes="h-10 w-10" />
         </div>
 
-        <div ref={node_ref} hidden={!*open} class="absolute bottom-4 left-14 z-10 w-44 divide-y divide-slate-200 rounded-md bg-white shadow-lg ring-1 ring-black ring-opacity-5">
+        <div ref={node_ref} hidden={!*open} class="absolute bottom-14 right-1 md:bottom-4 md:right-auto md:left-14 z-10 w-44 divide-y divide-slate-200 rounded-md bg-surface shadow-lg ring-1 ring-black ring-opacity-5">
             <div class="py-1.5 text-center text-slate-400">
                 {auth.user.username.clone()}
             </div>
             <div class="py-1">
-                <div class="px-4 py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-slate-50">
-                    {"Upadte Profile"}
+                <div onclick={open_modal(AccountModal::Profile)} class="px-4 py-3 md:py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-white">
+                    {lang.t("update-profile")}
                 </div>
-                <div class="px-4 py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-slate-50">
-                    {"Change Password"}
+                <div onclick={open_modal(AccountModal::Password)} class="px-4 py-3 md:py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-white">
+                    {lang.t("change-password")}
+                </div>
+                <div onclick={open_modal(AccountModal::Settings)} class="px-4 py-3 md:py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-white">
+                    {lang.t("settings")}
                 </div>
             </div>
+            {show_admin}
             <div class="py-1">
-                <div onclick={onlogout} class="px-4 py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-slate-50">
-                    {"Logout"}
+                <div onclick={onlogout} class="px-4 py-3 md:py-2 cursor-pointer text-sm text-slate-600 hover:bg-sky-600 hover:text-white">
+                    {lang.t("logout")}
                 </div>
             </div>
         </div>
+
+        {show_modal}
         </>
     }
 }
@@ -1,6 +1,16 @@
-use crate::{pages::route::Route, states::auth::AuthContext};
-use yew::{function_component, html, use_context, Children, Html, Properties};
-use yew_router::prelude::Redirect;
+use super::login_modal::LoginModal;
+use crate::{
+    hooks::use_token_refresh,
+    pages::route::{Destination, Route},
+    states::{
+        auth::AuthContext,
+        settings::{SettingsAction, SettingsContext},
+    },
+};
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, Children, Html, Properties,
+};
+use yew_router::prelude::{use_location, use_navigator, Redirect};
 
 // ========================// AuthGuard //======================== //
 
@@ -12,10 +22,73 @@ pub struct AuthGuardProps {
 #[function_component]
 pub fn AuthGuard(props: &AuthGuardProps) -> Html {
     let auth = use_context::<AuthContext>().expect("no auth context");
+    let settings = use_context::<SettingsContext>().unwrap();
+    use_token_refresh();
+
+    // every user keeps their own settings
+    use_effect_with_deps(
+        move |&(authorized, user_id)| {
+            if authorized && settings.user_id != Some(user_id) {
+                settings.dispatch(SettingsAction::Load(user_id));
+            }
+        },
+        (auth.authorized, auth.user.id),
+    );
 
     if auth.authorized {
-        html! {<>{ props.children.clone() }</>}
+        html! {
+            <>
+                { props.children.clone() }
+                if auth.expired {
+                    <LoginModal />
+                }
+            </>
+        }
     } else {
-        html! {<Redirect<Route> to={Route::Login}/>}
+        html! {<LoginRedirect />}
     }
 }
+
+// ========================// AdminGuard //======================== //
+
+#[function_component]
+pub fn AdminGuard(props: &AuthGuardProps) -> Html {
+    let auth = use_context::<AuthContext>().expect("no auth context");
+    use_token_refresh();
+
+    if !auth.authorized {
+        html! {<LoginRedirect />}
+    } else if auth.user.role == "admin" {
+        html! {
+            <>
+                { props.children.clone() }
+                if auth.expired {
+                    <LoginModal />
+                }
+            </>
+        }
+    } else {
+        html! {<Redirect<Route> to={Route::Chat}/>}
+    }
+}
+
+// ========================// LoginRedirect //======================== //
+
+/// Send the user to the login page, which returns here once logged in
+#[function_component]
+fn LoginRedirect() -> Html {
+    let navigator = use_navigator().unwrap();
+    let location = use_location().unwrap();
+
+    use_effect_with_deps(
+        move |_| {
+            let next = location.path().to_owned();
+            if let Err(e) = navigator.replace_with_query(&Route::Login, &Destination { next }) {
+                gloo_console::error!("Failed to redirect to the login page:", e.to_string());
+            }
+        },
+        (),
+    );
+
+    html! {}
+}
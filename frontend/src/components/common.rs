@@ -1,4 +1,13 @@
-use yew::{classes, function_component, html, use_state_eq, AttrValue, Classes, Html, Properties};
+use crate::{
+    hooks::{use_lang, use_pane},
+    i18n::Lang,
+    states::lang::{LangAction, LangContext},
+};
+use web_sys::HtmlSelectElement;
+use yew::{
+    classes, function_component, html, use_context, use_state_eq, AttrValue, Classes, Event, Html,
+    Properties, TargetCast,
+};
 
 // ========================// Avatar //======================== //
 
@@ -33,3 +42,70 @@ pub fn Avatar(props: &AvatarProps) -> Html {
         }
     }
 }
+
+// ========================// LangSelect //======================== //
+
+#[derive(PartialEq, Properties)]
+pub struct LangSelectProps {
+    #[prop_or_default]
+    pub classes: Classes,
+}
+
+/// Switch the language of the UI, each language is named in itself
+#[function_component]
+pub fn LangSelect(props: &LangSelectProps) -> Html {
+    let lang = use_context::<LangContext>().unwrap();
+
+    let onchange = {
+        let lang = lang.clone();
+        move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                if let Some(picked) = Lang::from_code(&select.value()) {
+                    lang.dispatch(LangAction::Set(picked));
+                }
+            }
+        }
+    };
+
+    let options = Lang::ALL
+        .iter()
+        .map(|x| {
+            html! {
+                <option value={x.code()} selected={lang.lang == *x}>{x.label()}</option>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <select {onchange}
+            class={classes!("rounded-md", "py-0.5", "px-1", "text-sm", "text-slate-600", "bg-surface", "ring-1", "ring-slate-300", "focus:outline-none", "focus:ring-sky-600", props.classes.clone())}>
+            {options}
+        </select>
+    }
+}
+
+// ========================// BackButton //======================== //
+
+#[derive(PartialEq, Properties)]
+pub struct BackButtonProps {
+    #[prop_or_default]
+    pub classes: Classes,
+}
+
+/// Return to the pane before, only shown on a narrow viewport
+#[function_component]
+pub fn BackButton(props: &BackButtonProps) -> Html {
+    let lang = use_lang();
+    let pane = use_pane();
+    let onclick = move |_| pane.back();
+
+    html! {
+        <button type="button" {onclick} title={lang.t("back")}
+            class={classes!("md:hidden", "shrink-0", "w-11", "h-11", "rounded-full", "text-slate-500", "hover:bg-slate-200", "active:text-sky-500", "flex", "items-center", "justify-center", props.classes.clone())}>
+            <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
+                stroke="currentColor" class="w-5 h-5">
+                <path stroke-linecap="round" stroke-linejoin="round" d="M15.75 19.5L8.25 12l7.5-7.5" />
+            </svg>
+        </button>
+    }
+}
@@ -1,5 +1,8 @@
 use super::{common::Avatar, search_box::SearchBox};
-use crate::states::{chat::ChatState, friend::FriendListTrigger, types::FriendItem};
+use crate::{
+    hooks::{use_lang, use_subscribe},
+    states::{chat::ChatState, store::Topic, types::FriendItem},
+};
 use yew::{
     function_component, html, use_memo, use_state, Callback, Html, Properties, UseStateHandle,
 };
@@ -9,14 +12,15 @@ use yew::{
 #[derive(PartialEq, Properties)]
 pub struct FriendListProps {
     pub chat: ChatState,
-    pub trigger: FriendListTrigger,
     pub curr_friend_id: UseStateHandle<i64>,
     pub nav_friend: Callback<i64>,
 }
 
 #[function_component]
 pub fn FriendList(props: &FriendListProps) -> Html {
-    let friends = use_memo(|_| props.chat.get_accepted_friends(), props.trigger.clone());
+    let lang = use_lang();
+    let version = use_subscribe(props.chat.store(), vec![Topic::Friends]);
+    let friends = use_memo(|_| props.chat.get_accepted_friends(), version);
 
     let onnewfriend = {
         let nav_friend = props.nav_friend.clone();
@@ -24,7 +28,7 @@ pub fn FriendList(props: &FriendListProps) -> Html {
     };
 
     let searching = use_state(|| false);
-    let searched = use_state(|| Vec::new());
+    let searched = use_state(Vec::new);
 
     let onenter = {
         let chat = props.chat.clone();
@@ -64,17 +68,17 @@ pub fn FriendList(props: &FriendListProps) -> Html {
         <>
         <div class="px-3 my-2 w-full">
             <SearchBox searching={searching.clone()} onenter={onenter.clone()}
-                placeholder={"Search friends..."}/>
+                placeholder={lang.t("friends-search")}/>
         </div>
 
         <div class="my-3 px-5 flex justify-between items-center">
             <p class="text-slate-500 font-semibold text-xs">
-                {"MY FRIENDS"}
+                {lang.t("friends-title")}
                 <span class="ml-2 text-sky-600 font-bold">
                     {if *searching {(*searched).len()} else {friends.len()}}
                 </span>
             </p>
-            <div onclick={onnewfriend} class="cursor-pointer rounded-full p-2 text-slate-500 hover:text-sky-600 hover:bg-slate-300 active:text-sky-500">
+            <div onclick={onnewfriend} class="cursor-pointer rounded-full p-3 md:p-2 text-slate-500 hover:text-sky-600 hover:bg-slate-300 active:text-sky-500">
                 <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
                     stroke="currentColor" class="w-5 h-5">
                     <path stroke-linecap="round" stroke-linejoin="round"
@@ -112,11 +116,11 @@ fn FriendEntry(props: &FriendEntryProps) -> Html {
 
     if props.is_selected {
         html! {
-            <li class="group bg-sky-600 cursor-pointer p-3 flex w-full items-center space-x-3">
+            <li {onclick} class="group bg-sky-600 cursor-pointer p-3 flex w-full items-center space-x-3">
                 <div class="shrink-0 rounded-full group-hover:ring-2 group-hover:ring-sky-100">
                     <Avatar image={props.friend.avatar.clone()} classes="h-10 w-10" />
                 </div>
-                <div class="min-w-0 truncate text-slate-50 font-semibold ">
+                <div class="min-w-0 truncate text-white font-semibold ">
                     {props.friend.nickname.clone()}
                 </div>
             </li>
@@ -127,7 +131,7 @@ fn FriendEntry(props: &FriendEntryProps) -> Html {
                 <div class="shrink-0 rounded-full">
                     <Avatar image={props.friend.avatar.clone()} classes="h-10 w-10" />
                 </div>
-                <div class="min-w-0 truncate text-slate-600 group-hover:text-slate-50 font-semibold ">
+                <div class="min-w-0 truncate text-slate-600 group-hover:text-white font-semibold ">
                     {props.friend.nickname.clone()}
                 </div>
             </li>
@@ -1,14 +1,16 @@
-use super::{common::Avatar, friend_list::FriendList, new_friend::NewFriend};
+use super::{
+    common::{Avatar, BackButton},
+    friend_list::FriendList,
+    new_friend::NewFriend,
+};
 use crate::{
-    pages::ChatPage,
-    states::{
-        chat::ChatState,
-        friend::{CurrFriendTrigger, FriendPageTrigger},
-        types::Relation,
-    },
+    hooks::{use_lang, use_pane, use_subscribe},
+    pages::{route::Route, ChatPage, Pane},
+    states::{chat::ChatState, store::Topic, types::Relation},
 };
 use yew::{
-    function_component, html, use_memo, use_state_eq, Callback, Html, Properties, UseStateHandle,
+    classes, function_component, html, use_effect_with_deps, use_memo, use_state_eq, Callback,
+    Html, Properties, UseStateHandle,
 };
 
 // ========================// FriendPage //======================== //
@@ -16,48 +18,66 @@ use yew::{
 #[derive(PartialEq, Properties)]
 pub struct FriendPageProps {
     pub chat: ChatState,
-    pub trigger: FriendPageTrigger,
+    // friend of the route, `-1` for the new friend form
+    pub friend_id: i64,
     pub nav_page: Callback<ChatPage>,
 }
 
 #[function_component]
 pub fn FriendPage(props: &FriendPageProps) -> Html {
-    let curr_friend_id = use_state_eq(|| props.chat.curr_friend());
+    let curr_friend_id = use_state_eq(|| props.friend_id);
+    let pane = use_pane();
+    // follow a friend opened by a link or the history of the browser
+    {
+        let chat = props.chat.clone();
+        let curr_friend_id = curr_friend_id.clone();
+        use_effect_with_deps(
+            move |friend_id| {
+                chat.set_curr_friend(*friend_id);
+                curr_friend_id.set(*friend_id);
+                || ()
+            },
+            props.friend_id,
+        );
+    }
 
     let nav_friend = {
         let curr_friend_id = curr_friend_id.clone();
         let chat = props.chat.clone();
+        let pane = pane.clone();
 
         Callback::from(move |friend_id: i64| {
             chat.set_curr_friend(friend_id);
             curr_friend_id.set(friend_id);
+            pane.goto(Route::friend(friend_id), Pane::Conversation);
         })
     };
 
     html! {
         <>
         // middle friend list
-        <div class="shrink-0 h-full w-64 pt-3 border-r bg-slate-200 flex flex-col">
-            <FriendList chat={props.chat.clone()} trigger={props.trigger.to_friends()}
+        <div class={classes!("shrink-0", "h-full", "w-full", "md:w-64", "pt-3", "border-r", "bg-slate-200", "flex-col", pane.display(Pane::List))}>
+            <FriendList chat={props.chat.clone()}
                 curr_friend_id={curr_friend_id.clone()} nav_friend={nav_friend.clone()} />
         </div>
 
         // right detail box
+        <div class={classes!("grow", "min-w-0", "h-full", pane.display(Pane::Conversation))}>
         {
             if *curr_friend_id == -1 {
-                html! {<NewFriend chat={props.chat.clone()} trigger={props.trigger.to_new()}
+                html! {<NewFriend chat={props.chat.clone()}
                     nav_page={props.nav_page.clone()} />}
             } else {
                 html! {
                     <div class="grow h-full bg-slate-100">
                         <CurrFriend chat={props.chat.clone()}
-                            trigger={props.trigger.to_curr()}
                             nav_page={props.nav_page.clone()}
                             curr_friend_id={curr_friend_id.clone()} />
                     </div>
                 }
             }
         }
+        </div>
         </>
     }
 }
@@ -67,16 +87,18 @@ pub fn FriendPage(props: &FriendPageProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct CurrFriendProps {
     chat: ChatState,
-    trigger: CurrFriendTrigger,
     curr_friend_id: UseStateHandle<i64>,
     nav_page: Callback<ChatPage>,
 }
 
 #[function_component]
 fn CurrFriend(props: &CurrFriendProps) -> Html {
+    let lang = use_lang();
+    let friend_id = *props.curr_friend_id;
+    let version = use_subscribe(props.chat.store(), vec![Topic::Friend(friend_id)]);
     let friend = use_memo(
         |(friend_id, _)| props.chat.get_curr_friend(*friend_id),
-        (*props.curr_friend_id.clone(), props.trigger.clone()),
+        (friend_id, version),
     );
 
     if let Some(ref friend) = *friend {
@@ -100,8 +122,9 @@ fn CurrFriend(props: &CurrFriendProps) -> Html {
 
         html! {
             <>
-            <div class="shrink-0 h-14 border-b px-4 flex items-center justify-center">
-                <p class="text-slate-600 font-semibold">{"Information"}</p>
+            <div class="relative shrink-0 h-14 border-b px-4 flex items-center justify-center">
+                <BackButton classes="absolute left-2" />
+                <p class="text-slate-600 font-semibold">{lang.t("friend-info")}</p>
             </div>
             <div class="pt-16 flex flex-col items-center space-y-5">
                 <div class="shrink-0 rounded-full">
@@ -112,18 +135,18 @@ fn CurrFriend(props: &CurrFriendProps) -> Html {
                 </p>
                 <div>
                     <p class="text-slate-500">
-                        {"Nick:"}
+                        {lang.t("friend-nick")}
                         <span class="ml-2">{friend.nickname.clone()}</span>
                     </p>
 
                 </div>
                 <button type="button" onclick={onchat}
                     class="rounded-md px-6 py-1 border border-sky-600 text-sky-600 hover:bg-sky-600 hover:text-white active:bg-sky-400">
-                    {"Chat"}
+                    {lang.t("chat")}
                 </button>
                 <button type="button" onclick={ondelete}
                     class="rounded-md px-6 py-1 border border-rose-500 text-rose-500 hover:bg-rose-500 hover:text-white active:bg-rose-300">
-                    {"Delete"}
+                    {lang.t("delete")}
                 </button>
             </div>
             </>
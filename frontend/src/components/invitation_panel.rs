@@ -0,0 +1,132 @@
+use crate::{
+    api::config::ApiConfig,
+    hooks::{use_lang, use_request, UseRequestHandle},
+    i18n,
+    states::{
+        toast::{ToastAction, ToastContext},
+        types::time_date,
+    },
+    validation::first_validation_error,
+};
+use shared::common::{CreateInvitationRequest, CreateInvitationResponse};
+use validator::Validate;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::HtmlInputElement;
+use yew::{function_component, html, use_context, use_node_ref, AttrValue, Html, NodeRef};
+
+#[wasm_bindgen]
+extern "C" {
+    // the Clipboard API of web-sys is behind `web_sys_unstable_apis`
+    #[wasm_bindgen(catch, js_namespace = ["navigator", "clipboard"], js_name = writeText)]
+    fn write_text(text: &str) -> Result<js_sys::Promise, JsValue>;
+}
+
+// ========================// InvitationPanel //======================== //
+
+#[function_component]
+pub fn InvitationPanel() -> Html {
+    let lang = use_lang();
+    let toast = use_context::<ToastContext>().unwrap();
+    let create: UseRequestHandle<CreateInvitationRequest, CreateInvitationResponse> =
+        use_request(ApiConfig::CreateInvitation);
+
+    let length_ref = use_node_ref();
+    let days_ref = use_node_ref();
+
+    let ongenerate = {
+        let create = create.clone();
+        let toast = toast.clone();
+        let length_ref = length_ref.clone();
+        let days_ref = days_ref.clone();
+
+        move |_| {
+            let value = |node: &NodeRef| {
+                node.cast::<HtmlInputElement>()
+                    .map(|x| x.value().trim().to_owned())
+                    .unwrap_or_default()
+            };
+            let invalid = |subject: &str| {
+                let msg = i18n::tr("field-invalid", &[("subject", &i18n::t(subject))]);
+                toast.dispatch(ToastAction::Error(msg.into()));
+            };
+
+            // only whole numbers, a negative count of days is not wrapped
+            let req = match (value(&length_ref).parse(), value(&days_ref).parse()) {
+                (Ok(length), Ok(days)) => CreateInvitationRequest { length, days },
+                (Err(_), _) => return invalid("invite-length"),
+                (_, Err(_)) => return invalid("invite-days"),
+            };
+            if let Err(e) = req.validate() {
+                let fields = [("length", "invite-length"), ("days", "invite-days")];
+                let msg = first_validation_error(&e, &fields);
+                toast.dispatch(ToastAction::Error(msg.into()));
+                return;
+            }
+            create.send(req);
+        }
+    };
+
+    let oncopy = {
+        let code = (*create)
+            .as_ref()
+            .map(|x| x.invitation.code.clone())
+            .unwrap_or_default();
+
+        move |_| {
+            let code = code.clone();
+            let toast = toast.clone();
+            spawn_local(async move {
+                let written = match write_text(&code) {
+                    Ok(promise) => JsFuture::from(promise).await,
+                    Err(e) => Err(e),
+                };
+                match written {
+                    Ok(_) => toast.dispatch(ToastAction::Info(AttrValue::from(i18n::t("copied")))),
+                    Err(e) => gloo_console::error!("Failed to copy the invitation code:", e),
+                }
+            });
+        }
+    };
+
+    let show_invitation = if let Some(ref data) = *create {
+        html! {
+            <div class="flex items-center space-x-3">
+                <input type="text" readonly={true} value={data.invitation.code.clone()}
+                    class="w-full py-1 px-2 rounded-md bg-slate-100 font-mono text-slate-700 border-2 border-slate-300 focus:outline-none" />
+                <button type="button" onclick={oncopy}
+                    class="shrink-0 rounded-md px-4 py-1 border border-sky-600 text-sm text-sky-600 hover:bg-sky-600 hover:text-white active:bg-sky-400">
+                    {lang.t("invite-copy")}
+                </button>
+                <span class="shrink-0 text-xs text-slate-400">
+                    {lang.tr("invite-expires", &[("date", &time_date(&data.invitation.expire_at))])}
+                </span>
+            </div>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class="rounded-md bg-surface shadow-sm">
+            <div class="px-4 py-3 border-b">
+                <p class="text-slate-500 font-semibold text-xs">{lang.t("invite-title")}</p>
+            </div>
+            <div class="px-4 py-3 space-y-3">
+                <div class="flex items-center space-x-3 text-sm text-slate-500">
+                    <label for="invite-length" class="shrink-0 font-semibold">{lang.t("invite-length")}</label>
+                    <input id="invite-length" type="number" min="4" max="50" value="8" ref={length_ref}
+                        class="w-20 py-1 px-2 rounded-md bg-surface text-slate-700 border-2 border-slate-300 focus:outline-none invalid:border-rose-500 focus:border-sky-600" />
+                    <label for="invite-days" class="shrink-0 font-semibold">{lang.t("invite-days")}</label>
+                    <input id="invite-days" type="number" min="1" max="365" value="7" ref={days_ref}
+                        class="w-20 py-1 px-2 rounded-md bg-surface text-slate-700 border-2 border-slate-300 focus:outline-none invalid:border-rose-500 focus:border-sky-600" />
+                    <button type="button" onclick={ongenerate} disabled={create.loading()}
+                        class="shrink-0 rounded-md px-4 py-1 border border-sky-600 bg-sky-600 text-white hover:bg-sky-700 active:bg-sky-600 disabled:cursor-wait">
+                        {lang.t("invite-generate")}
+                    </button>
+                </div>
+                {show_invitation}
+            </div>
+        </div>
+    }
+}
@@ -1,18 +1,47 @@
-use yew::{function_component, html, Html, Properties};
+use crate::{
+    hooks::{use_lang, use_reauth},
+    states::auth::AuthContext,
+};
+use web_sys::HtmlInputElement;
+use yew::{function_component, html, use_context, use_node_ref, Html};
 
-#[derive(PartialEq, Properties)]
-pub struct LoginModalProps {}
+// ========================// LoginModal //======================== //
 
 #[function_component]
-pub fn LoginModal(props: &LoginModalProps) -> Html {
-    let LoginModalProps {} = props;
+pub fn LoginModal() -> Html {
+    let lang = use_lang();
+    let auth = use_context::<AuthContext>().unwrap();
+    let reauth = use_reauth();
+    let password_ref = use_node_ref();
+
+    let oncancel = {
+        let reauth = reauth.clone();
+        move |_| reauth.cancel()
+    };
+
+    let onsubmit = {
+        let reauth = reauth.clone();
+        let password_ref = password_ref.clone();
+
+        move |_| {
+            let password = password_ref
+                .cast::<HtmlInputElement>()
+                .map(|x| x.value())
+                .unwrap_or_default();
+
+            if !password.is_empty() {
+                reauth.run(password);
+            }
+        }
+    };
 
     html! {
-        <div class="relative z-20" role="auth">
+        <div class="relative z-30" role="dialog">
             <div class="fixed inset-0 bg-slate-400 bg-opacity-75 transition-opacity"></div>
             <div class="fixed inset-0 p-5 flex min-h-full items-center justify-center">
-                <div class="w-96 transform overflow-hidden rounded-lg shadow-xl transition-all">
-                    <div class="bg-white px-4 py-5 space-y-4">
+                <form onsubmit={onsubmit} action="javascript:void(0);"
+                    class="w-96 transform overflow-hidden rounded-lg shadow-xl transition-all">
+                    <div class="bg-surface px-4 py-5 space-y-4">
                         <div class="flex items-center justify-center space-x-4">
                             <div class="rounded-full w-10 h-10 bg-red-100 flex items-center justify-center">
                                 <svg class="h-6 w-6 text-rose-600" fill="none" viewBox="0 0 24 24" stroke-width="1.5"
@@ -22,23 +51,25 @@ pub fn LoginModal(props: &LoginModalProps) -> Html {
                                 </svg>
                             </div>
                             <h3 class="text-base font-semibold leading-6 text-slate-900">
-                                {"Login expired"}</h3>
+                                {lang.t("login-expired")}</h3>
                         </div>
+                        <p class="text-center text-sm text-slate-500">
+                            {lang.tr("login-expired-desc", &[("name", &auth.user.username)])}
+                        </p>
                         <div class="p-4 flex items-center justify-center space-x-3">
-                            <label for="input-passwd" class="text-sm font-semibold text-slate-800">{"Password"}</label>
-                            <input id="input-passwd" type="password"
-                                class="w-full px-3 rounded-md border-0 py-1.5 text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 placeholder:text-gray-400 focus:ring-2 focus:ring-sky-600" />
+                            <label for="input-passwd" class="text-sm font-semibold text-slate-800">{lang.t("password")}</label>
+                            <input id="input-passwd" type="password" minlength="6" maxlength="50" ref={password_ref}
+                                class="w-full px-3 rounded-md border-0 py-1.5 text-slate-900 shadow-sm ring-1 ring-inset ring-slate-300 placeholder:text-slate-400 focus:ring-2 focus:ring-sky-600" />
                         </div>
                     </div>
                     <div class="bg-slate-100 px-5 py-3 flex items-center justify-end space-x-4">
-                        <button type="button"
-                            class="rounded-md bg-white px-3 py-2 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-slate-300 hover:bg-slate-200">{"Cancel"}</button>
-                        <button type="button"
-                            class="rounded-md bg-sky-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-sky-500">{"Submit"}</button>
+                        <button type="button" onclick={oncancel}
+                            class="rounded-md bg-surface px-3 py-2 text-sm font-semibold text-slate-900 shadow-sm ring-1 ring-slate-300 hover:bg-slate-200">{lang.t("cancel")}</button>
+                        <button type="submit" disabled={reauth.loading()}
+                            class="rounded-md bg-sky-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-sky-500 disabled:cursor-wait disabled:bg-cyan-700">{lang.t("submit")}</button>
                     </div>
-                </div>
+                </form>
             </div>
-
         </div>
     }
 }
@@ -1,8 +1,11 @@
 use super::{common::Avatar, search_box::SearchBox};
-use crate::states::{
-    chat::ChatState,
-    room::MemberListTrigger,
-    types::{FriendItem, MemberItem, Relation},
+use crate::{
+    hooks::{use_lang, use_subscribe},
+    states::{
+        chat::ChatState,
+        store::Topic,
+        types::{FriendItem, MemberItem, Relation},
+    },
 };
 use yew::{
     classes, function_component, html, use_memo, use_state, AttrValue, Callback, Html, Properties,
@@ -14,16 +17,22 @@ use yew::{
 #[derive(PartialEq, Properties)]
 pub struct MemberListProps {
     pub chat: ChatState,
-    pub trigger: MemberListTrigger,
     pub curr_room_id: UseStateHandle<i64>,
     pub nav_room: Callback<i64>,
 }
 
 #[function_component]
 pub fn MemberList(props: &MemberListProps) -> Html {
+    let lang = use_lang();
+    let room_id = *props.curr_room_id;
+    // relations to the members follow the friends
+    let version = use_subscribe(
+        props.chat.store(),
+        vec![Topic::Members(room_id), Topic::Friends],
+    );
     let rank = use_memo(
-        |_| props.chat.get_rank(*props.curr_room_id),
-        props.trigger.clone(),
+        |(room_id, _)| props.chat.get_rank(*room_id),
+        (room_id, version),
     );
 
     let open_adding = use_state(|| false);
@@ -65,8 +74,8 @@ pub fn MemberList(props: &MemberListProps) -> Html {
 
     // show members or searched list
     let members = use_memo(
-        |_| props.chat.get_members(*props.curr_room_id),
-        props.trigger.clone(),
+        |(room_id, _)| props.chat.get_members(*room_id),
+        (room_id, version),
     );
 
     let searching = use_state(|| false);
@@ -128,14 +137,14 @@ pub fn MemberList(props: &MemberListProps) -> Html {
         html! {
             <button type="button" onclick={ondelete}
                 class="rounded-md px-2 border border-rose-500 text-rose-500 hover:bg-rose-500 hover:text-white active:bg-rose-300">
-                {"Delete"}
+                {lang.t("delete")}
             </button>
         }
     } else {
         html! {
             <button type="button" onclick={onleave}
                 class="rounded-md px-2 border border-rose-500 text-rose-500 hover:bg-rose-500 hover:text-white active:bg-rose-300">
-                {"Leave"}
+                {lang.t("leave")}
             </button>
         }
     };
@@ -145,13 +154,13 @@ pub fn MemberList(props: &MemberListProps) -> Html {
         <div class="px-3 my-3 w-full">
             <div class="relative">
                 <SearchBox searching={searching.clone()} onenter={onenter.clone()}
-                    placeholder={"Search members..."}/>
+                    placeholder={lang.t("members-search")}/>
             </div>
         </div>
 
         <div class="px-5 py-1 flex justify-between items-center">
             <p class="text-slate-500 font-bold text-xs">
-                {"MEMBERS"}
+                {lang.t("members-title")}
                 <span class="ml-2 text-sky-700">{members.len()}</span>
             </p>
             {show_switch}
@@ -160,7 +169,7 @@ pub fn MemberList(props: &MemberListProps) -> Html {
         {
             if *open_adding {
                 html! {
-                    <AddMember chat={props.chat.clone()} trigger={props.trigger.clone()}
+                    <AddMember chat={props.chat.clone()}
                         curr_room_id={props.curr_room_id.clone()} />
                 }
             } else {
@@ -276,16 +285,17 @@ fn MemberEntry(props: &MemberEntryProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct AddMemberProps {
     chat: ChatState,
-    trigger: MemberListTrigger,
     curr_room_id: UseStateHandle<i64>,
 }
 
 #[function_component]
 fn AddMember(props: &AddMemberProps) -> Html {
+    let lang = use_lang();
     // show friends who can be added
-    let friends = use_memo(|_| props.chat.get_accepted_friends(), props.trigger.clone());
+    let version = use_subscribe(props.chat.store(), vec![Topic::Friends]);
+    let friends = use_memo(|_| props.chat.get_accepted_friends(), version);
 
-    let selected: UseStateHandle<Vec<i64>> = use_state(|| Vec::new());
+    let selected: UseStateHandle<Vec<i64>> = use_state(Vec::new);
     let onselect = {
         let selected = selected.clone();
         Callback::from(move |id: i64| {
@@ -329,8 +339,8 @@ fn AddMember(props: &AddMemberProps) -> Html {
 
         <div class="shrink-0 h-12 border-t flex items-center justify-center">
             <button type="button" onclick={onsubmit}
-                class="rounded-md px-4 py-0.5 border border-sky-600 bg-sky-600 text-slate-50 hover:bg-sky-700 hover:text-white active:bg-sky-600">
-                {"Submit"}
+                class="rounded-md px-4 py-0.5 border border-sky-600 bg-sky-600 text-white hover:bg-sky-700 hover:text-white active:bg-sky-600">
+                {lang.t("submit")}
             </button>
         </div>
         </>
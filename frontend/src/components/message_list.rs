@@ -1,56 +1,313 @@
 use super::common::Avatar;
-use crate::states::{chat::ChatState, room::MessageListTrigger, types::MessageItem};
+use crate::{
+    drafts_key,
+    hooks::{use_lang, use_local_storage, use_subscribe},
+    i18n,
+    states::{
+        auth::AuthContext,
+        chat::ChatState,
+        command::Command,
+        store::Topic,
+        types::{MessageItem, PendingContent, SendStatus},
+    },
+    validation::validation_error,
+};
+use gloo_events::EventListener;
+use gloo_timers::callback::Timeout;
 use shared::message::NewMessageRequest;
-use web_sys::{HtmlElement, HtmlInputElement};
+use std::{collections::HashMap, ops::Range};
+use uuid::Uuid;
+use validator::Validate;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{HtmlElement, HtmlTextAreaElement, KeyboardEvent};
 use yew::{
-    function_component, html, use_effect, use_memo, use_node_ref, use_state, Html, Properties,
-    UseStateHandle,
+    classes, function_component, html, use_callback, use_context, use_effect, use_effect_with_deps,
+    use_force_update, use_mut_ref, use_node_ref, use_state_eq, AttrValue, Callback, Html,
+    Properties, UseStateHandle,
 };
 
+// estimated height of an item not rendered yet (px)
+const ESTIMATED_HEIGHT: f64 = 72.0;
+// extra height rendered above and below the visible area (px)
+const OVERSCAN: f64 = 600.0;
+// height of the view before it is measured (px)
+const DEFAULT_VIEWPORT: f64 = 800.0;
+// the view follows new messages when scrolled this close to the bottom (px)
+const FOLLOW_SLACK: f64 = 40.0;
+// the drafts are stored once the typing pauses this long (ms)
+const DRAFT_DELAY: u32 = 500;
+
+// ========================// Layout //======================== //
+
+/// Heights of the items of a room, measured once they are rendered
+struct Layout {
+    room_id: i64,
+    // by the key of the item, the positions move as dividers come and go
+    heights: HashMap<AttrValue, f64>,
+    // number of items at the last render
+    count: usize,
+    // number of items when the view was last at the bottom
+    seen: usize,
+    // the view sticks to the bottom and follows new messages
+    follow: bool,
+}
+
+impl Layout {
+    fn new(room_id: i64) -> Self {
+        Self {
+            room_id,
+            heights: HashMap::new(),
+            count: 0,
+            seen: 0,
+            follow: true,
+        }
+    }
+
+    fn height(&self, key: &AttrValue) -> f64 {
+        self.heights.get(key).copied().unwrap_or(ESTIMATED_HEIGHT)
+    }
+
+    /// Top of every item, followed by the total height
+    fn offsets(&self, keys: &[AttrValue]) -> Vec<f64> {
+        let mut offsets = Vec::with_capacity(keys.len() + 1);
+        let mut top = 0.0;
+        offsets.push(top);
+        for key in keys {
+            top += self.height(key);
+            offsets.push(top);
+        }
+        offsets
+    }
+
+    /// Items overlapping the visible area and its margins
+    fn window(offsets: &[f64], top: f64, height: f64) -> Range<usize> {
+        let count = offsets.len() - 1;
+        let start = offsets.partition_point(|&x| x <= top - OVERSCAN);
+        let end = offsets.partition_point(|&x| x < top + height + OVERSCAN);
+        let end = end.min(count);
+        start.saturating_sub(1).min(end)..end
+    }
+
+    /// Record the rendered height of an item, returns how much it changed
+    fn measure(&mut self, key: &AttrValue, height: f64) -> f64 {
+        let delta = height - self.height(key);
+        self.heights.insert(key.clone(), height);
+        delta
+    }
+}
+
 // ========================// MessageList //======================== //
 
 #[derive(PartialEq, Properties)]
 pub struct MessageListProps {
     pub chat: ChatState,
-    pub trigger: MessageListTrigger,
     pub curr_room_id: UseStateHandle<i64>,
 }
 
+/// Only the messages around the visible area are rendered, the others are
+/// replaced by a spacer sized from the measured or estimated heights
 #[function_component]
 pub fn MessageList(props: &MessageListProps) -> Html {
-    let messages = use_memo(
-        |(_, room_id)| props.chat.get_messages(*room_id),
-        (props.trigger.clone(), *props.curr_room_id),
+    let room_id = *props.curr_room_id;
+    use_subscribe(props.chat.store(), vec![Topic::Messages(room_id)]);
+    // the time dividers are written again in another language
+    use_lang();
+    let keys = props.chat.message_keys(room_id);
+    let count = keys.len();
+
+    let layout = use_mut_ref(|| Layout::new(room_id));
+    let scroll_top = use_state_eq(|| 0.0_f64);
+    let viewport = use_state_eq(|| DEFAULT_VIEWPORT);
+    let update = use_force_update();
+    let box_node_ref = use_node_ref();
+    let window_node_ref = use_node_ref();
+
+    {
+        let mut layout = layout.borrow_mut();
+        if layout.room_id != room_id {
+            *layout = Layout::new(room_id);
+        }
+        // back to the bottom when the current user sends a message
+        if count > layout.count {
+            let last = props.chat.get_messages(room_id, count - 1..count);
+            if let Some(MessageItem::Pending(_)) = last.last() {
+                layout.follow = true;
+            }
+        }
+        layout.count = count;
+        if layout.follow {
+            layout.seen = count;
+        }
+    }
+
+    // messages from others arrived since the user scrolled up
+    let unseen = {
+        let seen = layout.borrow().seen;
+        if seen < count {
+            props
+                .chat
+                .get_messages(room_id, seen..count)
+                .iter()
+                .filter(|x| matches!(x, MessageItem::Incoming(_)))
+                .count()
+        } else {
+            0
+        }
+    };
+    let onjump = {
+        let layout = layout.clone();
+        let update = update.clone();
+        move |_| {
+            layout.borrow_mut().follow = true;
+            update.force_update();
+        }
+    };
+
+    let offsets = layout.borrow().offsets(&keys);
+    let total = offsets[count];
+    let top = if layout.borrow().follow {
+        (total - *viewport).max(0.0)
+    } else {
+        *scroll_top
+    };
+    let range = Layout::window(&offsets, top, *viewport);
+    let messages = props.chat.get_messages(room_id, range.clone());
+
+    let onretry = use_callback(
+        |nonce: Uuid, chat| chat.retry_message(nonce),
+        props.chat.clone(),
+    );
+    let ondiscard = use_callback(
+        |nonce: Uuid, chat| chat.discard_message(nonce),
+        props.chat.clone(),
     );
 
-    let show_messages = messages
-        .iter()
-        .map(|message| {
-            html! {
-                <MessageEntry key={message.key().as_str()} message={message.clone()} />
+    let onscroll = {
+        let layout = layout.clone();
+        let scroll_top = scroll_top.clone();
+        let viewport = viewport.clone();
+        let box_node_ref = box_node_ref.clone();
+
+        Callback::from(move |_| {
+            if let Some(ele) = box_node_ref.cast::<HtmlElement>() {
+                let top = ele.scroll_top() as f64;
+                let height = ele.client_height() as f64;
+                let bottom = ele.scroll_height() as f64 - FOLLOW_SLACK;
+
+                layout.borrow_mut().follow = top + height >= bottom;
+                scroll_top.set(top);
+                viewport.set(height);
             }
         })
-        .collect::<Html>();
+    };
 
-    let box_node_ref = use_node_ref();
+    // the view changes size with the window
     {
+        let viewport = viewport.clone();
         let box_node_ref = box_node_ref.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                let window = web_sys::window().unwrap_throw();
+                let listener = EventListener::new(&window, "resize", move |_| {
+                    if let Some(ele) = box_node_ref.cast::<HtmlElement>() {
+                        viewport.set(ele.client_height() as f64);
+                    }
+                });
+
+                move || drop(listener)
+            },
+            (),
+        );
+    }
+
+    // measure the rendered items, keep what is being read in place, and
+    // render again if the estimates were off
+    {
+        let layout = layout.clone();
+        let box_node_ref = box_node_ref.clone();
+        let window_node_ref = window_node_ref.clone();
+        let offsets = offsets.clone();
+        let keys = keys.clone();
+
         use_effect(move || {
-            if let Some(ele) = box_node_ref.cast::<HtmlElement>() {
-                ele.scroll_to_with_x_and_y(0.0, ele.scroll_height() as f64);
+            if let (Some(ele), Some(window)) = (
+                box_node_ref.cast::<HtmlElement>(),
+                window_node_ref.cast::<HtmlElement>(),
+            ) {
+                let mut layout = layout.borrow_mut();
+                let top = ele.scroll_top() as f64;
+                let mut changed = false;
+                // growth of the items above the visible area
+                let mut shift = 0.0;
+
+                let children = window.children();
+                for i in 0..children.length() {
+                    let item = children
+                        .item(i)
+                        .and_then(|x| x.dyn_into::<HtmlElement>().ok());
+                    let idx = item.as_ref().and_then(|x| x.get_attribute("data-index"));
+                    let idx = idx.and_then(|x| x.parse::<usize>().ok());
+                    if let (Some(item), Some(idx)) = (item, idx.filter(|&x| x < keys.len())) {
+                        let delta = layout.measure(&keys[idx], item.offset_height() as f64);
+                        if delta.abs() >= 0.5 {
+                            changed = true;
+                            if offsets.get(idx).is_some_and(|&x| x < top) {
+                                shift += delta;
+                            }
+                        }
+                    }
+                }
+
+                if layout.follow {
+                    ele.set_scroll_top(ele.scroll_height());
+                } else if shift != 0.0 {
+                    ele.set_scroll_top((top + shift) as i32);
+                }
+                if changed {
+                    update.force_update();
+                }
             }
+            || ()
         });
     }
 
+    let show_messages = messages
+        .iter()
+        .enumerate()
+        .map(|(i, message)| {
+            html! {
+                <div key={message.key().as_str()} data-index={(range.start + i).to_string()} class="pb-5">
+                    <MessageEntry message={message.clone()}
+                        onretry={onretry.clone()} ondiscard={ondiscard.clone()} />
+                </div>
+            }
+        })
+        .collect::<Html>();
+
     html! {
         <>
         // chat content
-        <div ref={box_node_ref} class="grow p-3 flex flex-col space-y-5 overflow-y-scroll hover:scrollbar no-scrollbar">
-            {show_messages}
+        <div class="relative grow min-h-0">
+            <div ref={box_node_ref} {onscroll} class="h-full p-3 overflow-y-scroll hover:scrollbar no-scrollbar">
+                <div class="relative" style={format!("height: {}px", total)}>
+                    <div ref={window_node_ref} class="absolute inset-x-0"
+                        style={format!("top: {}px", offsets[range.start])}>
+                        {show_messages}
+                    </div>
+                </div>
+            </div>
+
+            if unseen > 0 {
+                <button type="button" onclick={onjump}
+                    class="absolute bottom-3 left-1/2 -translate-x-1/2 px-4 py-1 rounded-full shadow-md bg-sky-600 hover:bg-sky-700 text-xs font-semibold text-white">
+                    {i18n::tr("new-messages", &[("count", &unseen.to_string())])}
+                </button>
+            }
         </div>
 
         // chat sender
-        <SendMessage chat={props.chat.clone()} trigger={props.trigger.clone()}
+        <SendMessage chat={props.chat.clone()}
             curr_room_id={props.curr_room_id.clone()} />
 
         </>
@@ -62,22 +319,58 @@ pub fn MessageList(props: &MessageListProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct MessageEntryProps {
     message: MessageItem,
+    onretry: Callback<Uuid>,
+    ondiscard: Callback<Uuid>,
 }
 
 #[function_component]
 fn MessageEntry(props: &MessageEntryProps) -> Html {
+    let lang = use_lang();
+
     match props.message {
         MessageItem::TimeDivider(ref date) => {
             // divider for time
             html! {
                 <div class="text-center pt-7 pb-2">
                     <hr class="-mb-3.5" />
-                    <span class="px-3 py-0.5 -mt-3.5 rounded-lg bg-white text-xs text-slate-400">
+                    <span class="px-3 py-0.5 -mt-3.5 rounded-lg bg-surface text-xs text-slate-400">
                         {date.clone()}
                     </span>
                 </div>
             }
         }
+        MessageItem::UnreadDivider => {
+            // first message unread when the room was opened
+            html! {
+                <div class="text-center pt-3">
+                    <hr class="-mb-3 border-rose-300" />
+                    <span class="px-3 py-0.5 rounded-lg bg-surface text-xs font-semibold text-rose-400">
+                        {lang.t("unread-divider")}
+                    </span>
+                </div>
+            }
+        }
+        MessageItem::Incoming(ref item) | MessageItem::Outgoing(ref item)
+            if item.kind == "action" =>
+        {
+            // what a member says they are doing, from /me
+            html! {
+                <p class="px-10 text-center text-sm italic text-slate-500">
+                    {format!("* {} {}", item.name, item.content)}
+                </p>
+            }
+        }
+        MessageItem::Pending(ref item) if item.kind == "action" => {
+            html! {
+                <div class="flex flex-col items-center">
+                    <p class="px-10 text-center text-sm italic text-slate-500 opacity-60">
+                        {format!("* {} {}", item.name, item.content)}
+                    </p>
+                    <PendingStatus item={item.clone()} onretry={props.onretry.clone()}
+                        ondiscard={props.ondiscard.clone()} />
+                </div>
+            }
+        }
         MessageItem::Outgoing(ref item) => {
             // message send by yourself
             html! {
@@ -116,9 +409,31 @@ fn MessageEntry(props: &MessageEntryProps) -> Html {
                                     {item.send_at.clone()}
                                 </span>
                             </p>
-                            <div class="w-fit px-3 py-2 bg-white shadow-sm rounded-b-lg rounded-tr-lg text-sm text-slate-700">
+                            <div class="w-fit px-3 py-2 bg-surface shadow-sm rounded-b-lg rounded-tr-lg text-sm text-slate-700">
+                                {item.content.clone()}
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            }
+        }
+        MessageItem::Pending(ref item) => {
+            // message waiting for the server, or failed to be sent
+            html! {
+                <div class="flex flex-row-reverse">
+                    <div class="w-5/6 flex flex-row-reverse items-start space-x-2 space-x-reverse">
+                        <div class="shrink-0 rounded-full">
+                            <Avatar image={item.avatar.clone()} classes={"h-8 w-8"} />
+                        </div>
+                        <div class="grow flex flex-col items-end">
+                            <p class="w-fit mb-2 font-semibold text-sm text-slate-700">
+                                {item.name.clone()}
+                            </p>
+                            <div class="w-fit px-3 py-2 bg-sky-600 opacity-60 shadow-md rounded-b-lg rounded-tl-lg text-sm text-white">
                                 {item.content.clone()}
                             </div>
+                            <PendingStatus item={item.clone()} onretry={props.onretry.clone()}
+                                ondiscard={props.ondiscard.clone()} />
                         </div>
                     </div>
                 </div>
@@ -127,27 +442,162 @@ fn MessageEntry(props: &MessageEntryProps) -> Html {
     }
 }
 
+// ========================// PendingStatus //======================== //
+
+#[derive(PartialEq, Properties)]
+struct PendingStatusProps {
+    item: PendingContent,
+    onretry: Callback<Uuid>,
+    ondiscard: Callback<Uuid>,
+}
+
+#[function_component]
+fn PendingStatus(props: &PendingStatusProps) -> Html {
+    let nonce = props.item.nonce;
+    let lang = use_lang();
+
+    match props.item.status {
+        SendStatus::Pending => html! {
+            <p class="mt-1 text-xs text-slate-400">{lang.t("message-sending")}</p>
+        },
+        SendStatus::Queued => {
+            let ondiscard = {
+                let ondiscard = props.ondiscard.clone();
+                move |_| ondiscard.emit(nonce)
+            };
+
+            html! {
+                <p class="mt-1 flex items-center space-x-2 text-xs">
+                    <span class="text-slate-400">{lang.t("message-waiting")}</span>
+                    <button type="button" onclick={ondiscard}
+                        class="font-semibold text-slate-400 hover:text-slate-600">{lang.t("message-discard")}</button>
+                </p>
+            }
+        }
+        SendStatus::Failed => {
+            let onretry = {
+                let onretry = props.onretry.clone();
+                move |_| onretry.emit(nonce)
+            };
+            let ondiscard = {
+                let ondiscard = props.ondiscard.clone();
+                move |_| ondiscard.emit(nonce)
+            };
+
+            html! {
+                <p class="mt-1 flex items-center space-x-2 text-xs">
+                    <span class="text-rose-500">{lang.t("message-failed")}</span>
+                    <button type="button" onclick={onretry}
+                        class="font-semibold text-sky-600 hover:text-sky-800">{lang.t("message-retry")}</button>
+                    <button type="button" onclick={ondiscard}
+                        class="font-semibold text-slate-400 hover:text-slate-600">{lang.t("message-discard")}</button>
+                </p>
+            }
+        }
+    }
+}
+
 // ========================// SendMessage //======================== //
 
 #[derive(PartialEq, Properties)]
 struct SendMessageListProps {
     chat: ChatState,
-    trigger: MessageListTrigger,
     curr_room_id: UseStateHandle<i64>,
 }
 
 #[function_component]
 fn SendMessage(props: &SendMessageListProps) -> Html {
+    let auth = use_context::<AuthContext>().unwrap();
+    let drafts = use_local_storage::<HashMap<i64, String>>(&drafts_key(auth.user.id));
     let input_node_ref = use_node_ref();
-    let input_value = use_state(String::default);
+    let error = use_state_eq(|| None::<String>);
+    let lang = use_lang();
+    let update = use_force_update();
+    // pending write of the drafts, cancelled when replaced or dropped
+    let save_timer = use_mut_ref(|| None::<Timeout>);
+
+    // the text shown is the draft of the room
+    let input_value = props.chat.get_draft(*props.curr_room_id);
+
+    // grow with the text, up to the max height of the textarea
+    {
+        let input_node_ref = input_node_ref.clone();
+        use_effect(move || {
+            if let Some(input) = input_node_ref.cast::<HtmlTextAreaElement>() {
+                let _ = input.set_attribute("style", "height: auto");
+                let height = format!("height: {}px", input.scroll_height());
+                let _ = input.set_attribute("style", &height);
+            }
+        });
+    }
+
+    // replace the text of the composer and keep it as the draft of the room
+    let set_text = {
+        let chat = props.chat.clone();
+        let curr_room_id = props.curr_room_id.clone();
+        let error = error.clone();
+
+        let save_timer = save_timer.clone();
+        let drafts = drafts.clone();
+
+        Callback::from(move |text: String| {
+            chat.set_draft(*curr_room_id, text);
+            error.set(None);
+            update.force_update();
+
+            let chat = chat.clone();
+            let drafts = drafts.clone();
+            let timer = Timeout::new(DRAFT_DELAY, move || drafts.set(chat.get_drafts()));
+            *save_timer.borrow_mut() = Some(timer);
+        })
+    };
+
+    // stored right away when the user leaves the composer, to another room or page
+    let onblur = {
+        let chat = props.chat.clone();
+
+        move |_| {
+            if save_timer.borrow_mut().take().is_some() {
+                drafts.set(chat.get_drafts());
+            }
+        }
+    };
+
+    // completions of the slash command being typed, hidden with Escape
+    let selected = use_state_eq(|| 0_usize);
+    let dismissed = use_state_eq(String::default);
+    let suggestions = if *dismissed == input_value {
+        Vec::new()
+    } else {
+        props
+            .chat
+            .complete_command(*props.curr_room_id, &input_value)
+    };
+    let selected_idx = (*selected).min(suggestions.len().saturating_sub(1));
+
+    let onpick = {
+        let set_text = set_text.clone();
+        let selected = selected.clone();
+        let input_node_ref = input_node_ref.clone();
+
+        Callback::from(move |value: AttrValue| {
+            set_text.emit(value.to_string());
+            selected.set(0);
+            if let Some(input) = input_node_ref.cast::<HtmlTextAreaElement>() {
+                let _ = input.focus();
+            }
+        })
+    };
 
     let oninput = {
-        let input_value = input_value.clone();
+        let set_text = set_text.clone();
+        let selected = selected.clone();
         let input_node_ref = input_node_ref.clone();
 
         move |_| {
-            if let Some(input) = input_node_ref.cast::<HtmlInputElement>() {
-                input_value.set(input.value());
+            if let Some(input) = input_node_ref.cast::<HtmlTextAreaElement>() {
+                set_text.emit(input.value());
+                selected.set(0);
             }
         }
     };
@@ -155,22 +605,130 @@ fn SendMessage(props: &SendMessageListProps) -> Html {
     let onsend = {
         let chat = props.chat.clone();
         let curr_room_id = props.curr_room_id.clone();
-        let input_value = input_value.clone();
+        let error = error.clone();
+        let input_node_ref = input_node_ref.clone();
 
-        move |_| {
-            let msg = NewMessageRequest {
-                room_id: *curr_room_id,
-                content: (*input_value).clone(),
-                kind: "text".to_owned(),
+        Callback::from(move |_| {
+            let content = chat.get_draft(*curr_room_id);
+            if content.trim().is_empty() {
+                return;
+            }
+
+            let sent = match Command::parse(&content) {
+                Some(Ok(command)) => chat.run_command(*curr_room_id, command),
+                Some(Err(e)) => Err(e),
+                None => {
+                    let msg = NewMessageRequest {
+                        room_id: *curr_room_id,
+                        content,
+                        kind: "text".to_owned(),
+                        nonce: None,
+                    };
+                    msg.validate()
+                        .map(|_| chat.send_message(msg))
+                        .map_err(|e| validation_error(&e, "content", &i18n::t("message-subject")))
+                }
             };
-            chat.send_message(msg);
-            input_value.set(String::default());
+
+            match sent {
+                Ok(_) => set_text.emit(String::default()),
+                Err(e) => error.set(Some(e)),
+            }
+            if let Some(input) = input_node_ref.cast::<HtmlTextAreaElement>() {
+                let _ = input.focus();
+            }
+        })
+    };
+
+    // Enter sends the message, Shift+Enter starts a new line, the arrows, Tab and
+    // Enter pick a completion while they are shown
+    let onkeydown = {
+        let onsend = onsend.clone();
+        let onpick = onpick.clone();
+        let picked = suggestions
+            .get(selected_idx)
+            .map(|x| x.value.clone())
+            .filter(|x| *x != input_value);
+        let count = suggestions.len();
+        let input_value = input_value.clone();
+
+        move |event: KeyboardEvent| {
+            if event.is_composing() {
+                return;
+            }
+            match event.key().as_str() {
+                "ArrowUp" | "ArrowDown" if count > 0 => {
+                    event.prevent_default();
+                    let step = if event.key() == "ArrowUp" {
+                        count - 1
+                    } else {
+                        1
+                    };
+                    selected.set((selected_idx + step) % count);
+                }
+                "Tab" | "Enter" if picked.is_some() && !event.shift_key() => {
+                    event.prevent_default();
+                    onpick.emit(picked.clone().unwrap_throw());
+                }
+                "Escape" if count > 0 => dismissed.set(input_value.clone()),
+                "Enter" if !event.shift_key() => {
+                    event.prevent_default();
+                    onsend.emit(());
+                }
+                _ => {}
+            }
         }
     };
 
+    let onclick = move |_| onsend.emit(());
+
+    let show_suggestions = if suggestions.is_empty() {
+        html! {}
+    } else {
+        let items = suggestions
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let onclick = {
+                    let onpick = onpick.clone();
+                    let value = item.value.clone();
+                    move |_| onpick.emit(value.clone())
+                };
+                let cls = if idx == selected_idx {
+                    "bg-sky-600 text-white"
+                } else {
+                    "text-slate-700 hover:bg-slate-100"
+                };
+
+                html! {
+                    <li {onclick} class={classes!("px-3", "py-1.5", "flex", "justify-between", "space-x-4", "cursor-pointer", cls)}>
+                        <span class="font-semibold text-sm truncate">{item.label.clone()}</span>
+                        <span class="shrink-0 text-xs opacity-70">{item.hint.clone()}</span>
+                    </li>
+                }
+            })
+            .collect::<Html>();
+
+        html! {
+            <ul class="absolute bottom-full inset-x-4 mb-1 py-1 bg-surface rounded-lg shadow-md border overflow-hidden">
+                {items}
+            </ul>
+        }
+    };
+
+    let show_error = match &*error {
+        Some(error) => html! {
+            <p class="px-4 pt-2 text-xs text-rose-500">{error}</p>
+        },
+        None => html! {},
+    };
+
     html! {
-        <div class="shrink-0 h-16 border-t px-4 flex justify-center items-center">
-            <div class="flex justify-between w-full items-center bg-white rounded-lg">
+        <div class="relative shrink-0 border-t">
+        {show_suggestions}
+        {show_error}
+        <div class="px-4 py-3 flex justify-center items-center">
+            <div class="flex justify-between w-full items-end py-1.5 bg-surface rounded-lg">
                 <div class="mx-2 rounded-full p-1 cursor-pointer text-slate-400 hover:text-sky-600 active:text-slate-400">
                     <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
                         stroke="currentColor" class="w-5 h-5">
@@ -178,9 +736,9 @@ fn SendMessage(props: &SendMessageListProps) -> Html {
                             d="M18.375 12.739l-7.693 7.693a4.5 4.5 0 01-6.364-6.364l10.94-10.94A3 3 0 1119.5 7.372L8.552 18.32m.009-.01l-.01.01m5.699-9.941l-7.81 7.81a1.5 1.5 0 002.112 2.13" />
                     </svg>
                 </div>
-                <input type="text" spellcheck="false" ref={input_node_ref} {oninput} value={(*input_value).clone()}
-                    class="w-full py-1 bg-transparent outline-none placeholder:text-slate-300 text-slate-700"
-                    placeholder="Type your message here..." />
+                <textarea rows="1" spellcheck="false" ref={input_node_ref} {oninput} {onkeydown} {onblur} value={input_value}
+                    class="w-full py-1 max-h-40 resize-none bg-transparent outline-none placeholder:text-slate-300 text-slate-700"
+                    placeholder={lang.t("message-placeholder")} />
                 <div class="mx-3 flex items-center space-x-3">
                     <div class="rounded-full p-1 cursor-pointer text-slate-400 hover:text-sky-600 active:text-slate-400">
                         <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
@@ -190,7 +748,7 @@ fn SendMessage(props: &SendMessageListProps) -> Html {
                         </svg>
                     </div>
 
-                    <div onclick={onsend} class="rounded-full p-2 cursor-pointer bg-sky-600 hover:bg-sky-800 active:bg-sky-600">
+                    <div {onclick} class="rounded-full p-2 cursor-pointer bg-sky-600 hover:bg-sky-800 active:bg-sky-600">
                         <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="currentColor"
                             class="w-5 h-5 fill-white -rotate-45">
                             <path d="M3.478 2.405a.75.75 0 00-.926.94l2.432 7.905H13.5a.75.75 0 010 1.5H4.984l-2.432 7.905a.75.75 0 00.926.94 60.519 60.519 0 0018.445-8.986.75.75 0 000-1.218A60.517 60.517 0 003.478 2.405z" />
@@ -200,5 +758,6 @@ fn SendMessage(props: &SendMessageListProps) -> Html {
                 </div>
             </div>
         </div>
+        </div>
     }
 }
@@ -3,13 +3,20 @@ pub mod auth_guard;
 pub mod common;
 pub mod friend_list;
 pub mod friend_page;
+pub mod invitation_panel;
 pub mod login_modal;
 pub mod member_list;
 pub mod message_list;
 pub mod new_friend;
 pub mod new_room;
+pub mod password_modal;
+pub mod profile_modal;
+pub mod quick_switcher;
 pub mod role_banner;
 pub mod room_list;
 pub mod room_page;
 pub mod search_box;
+pub mod settings_modal;
+pub mod theme_provider;
 pub mod toast;
+pub mod user_table;
@@ -1,11 +1,14 @@
-use super::{common::Avatar, search_box::SearchBox};
+use super::{
+    common::{Avatar, BackButton},
+    search_box::SearchBox,
+};
 use crate::{
     api::config::ApiConfig,
-    hooks::{use_request, UseRequestHandle},
+    hooks::{use_lang, use_request, use_subscribe, UseRequestHandle},
     pages::ChatPage,
     states::{
         chat::ChatState,
-        friend::NewFriendTrigger,
+        store::Topic,
         types::{FriendAttr, Relation},
     },
 };
@@ -17,14 +20,16 @@ use yew::{function_component, html, use_memo, use_state, Callback, Html, Propert
 #[derive(PartialEq, Properties)]
 pub struct NewFriendProps {
     pub chat: ChatState,
-    pub trigger: NewFriendTrigger,
     pub nav_page: Callback<ChatPage>,
 }
 
 #[function_component]
 pub fn NewFriend(props: &NewFriendProps) -> Html {
+    let lang = use_lang();
+    let version = use_subscribe(props.chat.store(), vec![Topic::Friends]);
+
     // show recieved friend invitation
-    let incoming_friends = use_memo(|_| props.chat.get_incoming_friends(), props.trigger.clone());
+    let incoming_friends = use_memo(|_| props.chat.get_incoming_friends(), version);
     let accept_friend = {
         let chat = props.chat.clone();
         Callback::from(move |friend_id: i64| chat.accept_friend(friend_id))
@@ -37,7 +42,7 @@ pub fn NewFriend(props: &NewFriendProps) -> Html {
     let show_incoming = if !incoming_friends.is_empty() {
         html! {
             <>
-            <p class="mt-4 ml-2 text-sm text-slate-400 font-semibold">{"Incoming"}</p>
+            <p class="mt-4 ml-2 text-sm text-slate-400 font-semibold">{lang.t("new-friend-incoming")}</p>
             <ul class="my-2 mx-2 space-y-1">
                 {
                     incoming_friends.iter().map(|item| {
@@ -55,11 +60,11 @@ pub fn NewFriend(props: &NewFriendProps) -> Html {
     };
 
     // show sent friend invitation
-    let outgoing_friends = use_memo(|_| props.chat.get_outgoing_friends(), props.trigger.clone());
+    let outgoing_friends = use_memo(|_| props.chat.get_outgoing_friends(), version);
     let show_outgoing = if !outgoing_friends.is_empty() {
         html! {
             <>
-            <p class="mt-4 ml-2 text-sm text-slate-400 font-semibold">{"Outgoing"}</p>
+            <p class="mt-4 ml-2 text-sm text-slate-400 font-semibold">{lang.t("new-friend-outgoing")}</p>
             <ul class="my-2 mx-2 space-y-1">
                 {
                     outgoing_friends.iter().map(|item| {
@@ -77,11 +82,12 @@ pub fn NewFriend(props: &NewFriendProps) -> Html {
 
     html! {
         <div class="grow h-full bg-slate-100">
-            <div class="shrink-0 h-14 border-b px-4 flex items-center justify-center">
-                <p class="text-slate-600 font-semibold">{"New friend"}</p>
+            <div class="relative shrink-0 h-14 border-b px-4 flex items-center justify-center">
+                <BackButton classes="absolute left-2" />
+                <p class="text-slate-600 font-semibold">{lang.t("new-friend")}</p>
             </div>
             <div class="max-w-md mx-auto p-6">
-                <FindNewFriend chat={props.chat.clone()} trigger={props.trigger.clone()} nav_page={props.nav_page.clone()} />
+                <FindNewFriend chat={props.chat.clone()} nav_page={props.nav_page.clone()} />
 
                 <div class="overflow-y-scroll hover:scrollbar no-scrollbar">
                     {show_incoming}
@@ -97,20 +103,21 @@ pub fn NewFriend(props: &NewFriendProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct FindNewFriendProps {
     chat: ChatState,
-    trigger: NewFriendTrigger,
     nav_page: Callback<ChatPage>,
 }
 
 #[function_component]
 fn FindNewFriend(props: &FindNewFriendProps) -> Html {
+    let lang = use_lang();
     let find_user: UseRequestHandle<GetUserByNameRequest, GetUserByNameResponse> =
         use_request(ApiConfig::GetUserByName);
+    // the relation to the found user changes with the friend requests
+    use_subscribe(props.chat.store(), vec![Topic::Friends]);
 
     let searching = use_state(|| false);
     let finding = (*find_user)
         .as_ref()
-        .map(|u| props.chat.get_friend_from_user(&u))
-        .flatten();
+        .and_then(|u| props.chat.get_friend_from_user(u));
 
     let onenter = {
         let find_user = find_user.clone();
@@ -194,7 +201,7 @@ fn FindNewFriend(props: &FindNewFriendProps) -> Html {
         } else {
             html! {
                 <div class="p-2 m-2 rounded-md cursor-default text-center font-semibold text-slate-400">
-                    {"Nothing to find"}
+                    {lang.t("new-friend-nothing")}
                 </div>
             }
         }
@@ -206,7 +213,7 @@ fn FindNewFriend(props: &FindNewFriendProps) -> Html {
         <>
         <div class="mx-2">
             <SearchBox searching={searching.clone()} onenter={onenter.clone()}
-                placeholder={"Find new friend..."}/>
+                placeholder={lang.t("new-friend-find")}/>
         </div>
         {show_finding}
         </>
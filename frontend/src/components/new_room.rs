@@ -1,5 +1,8 @@
-use super::common::Avatar;
-use crate::states::{chat::ChatState, room::NewRoomTrigger, types::FriendItem};
+use super::common::{Avatar, BackButton};
+use crate::{
+    hooks::{use_lang, use_subscribe},
+    states::{chat::ChatState, store::Topic, types::FriendItem},
+};
 use web_sys::HtmlInputElement;
 use yew::{
     function_component, html, use_memo, use_node_ref, use_state, AttrValue, Callback, Html,
@@ -10,16 +13,17 @@ use yew::{
 
 #[derive(PartialEq, Properties)]
 pub struct NewRoomProps {
-    pub trigger: NewRoomTrigger,
     pub chat: ChatState,
 }
 
 #[function_component]
 pub fn NewRoom(props: &NewRoomProps) -> Html {
-    let friends = use_memo(|_| props.chat.get_accepted_friends(), props.trigger.clone());
+    let lang = use_lang();
+    let version = use_subscribe(props.chat.store(), vec![Topic::Friends]);
+    let friends = use_memo(|_| props.chat.get_accepted_friends(), version);
 
     let input_node_ref = use_node_ref();
-    let input_value = use_state(|| String::default());
+    let input_value = use_state(String::default);
     let oninput = {
         let input_value = input_value.clone();
         let input_node_ref = input_node_ref.clone();
@@ -31,7 +35,7 @@ pub fn NewRoom(props: &NewRoomProps) -> Html {
         }
     };
 
-    let selected: UseStateHandle<Vec<i64>> = use_state(|| Vec::new());
+    let selected: UseStateHandle<Vec<i64>> = use_state(Vec::new);
     let onselect = {
         let selected = selected.clone();
         Callback::from(move |id: i64| {
@@ -63,28 +67,29 @@ pub fn NewRoom(props: &NewRoomProps) -> Html {
 
     html! {
         <div class="grow h-full py-1 bg-slate-100 flex flex-col items-center">
-            <div class="shrink-0 h-14 w-full border-b px-4 flex items-center justify-center">
-                <p class="text-slate-700 font-semibold">{"New room"}</p>
+            <div class="relative shrink-0 h-14 w-full border-b px-4 flex items-center justify-center">
+                <BackButton classes="absolute left-2" />
+                <p class="text-slate-700 font-semibold">{lang.t("new-room")}</p>
             </div>
 
             <div class="shrink-0 h-20 w-80 flex items-center justify-between">
-                <label for="room-name" class="block mr-3 min-w-0 shrink-0 text-slate-500 font-semibold">{"Name:"}</label>
+                <label for="room-name" class="block mr-3 min-w-0 shrink-0 text-slate-500 font-semibold">{lang.t("new-room-name")}</label>
                 <input id="room-name" type="text" ref={input_node_ref} value={(*input_value).clone()} {oninput}
-                    class="appearance-none w-full py-1 px-2 rounded-md bg-white text-slate-700 leading-tight border-2 border-slate-300 focus:outline-none focus:border-sky-600" />
+                    class="appearance-none w-full py-1 px-2 rounded-md bg-surface text-slate-700 leading-tight border-2 border-slate-300 focus:outline-none focus:border-sky-600" />
             </div>
 
             {
                 if friends.is_empty() {
                     html! {
                         <div class="w-80 p-1 font-semibold text-slate-400 text-center">
-                            {"You have no friends to create a room!"}
+                            {lang.t("new-room-no-friends")}
                         </div>
                     }
                 } else {
                     html! {
                         <>
                         <div class="w-80 p-1 overflow-y-scroll hover:scrollbar no-scrollbar">
-                            <ul class="py-2 rounded-md shadow-sm bg-white">
+                            <ul class="py-2 rounded-md shadow-sm bg-surface">
                                 {
                                     friends.iter().map(|item| {
                                         html! {
@@ -96,8 +101,8 @@ pub fn NewRoom(props: &NewRoomProps) -> Html {
                         </div>
                         <div class="shrink-0 h-16 flex items-center justify-center">
                             <button type="button" onclick={onsubmit}
-                                class="rounded-md px-6 py-1 border border-sky-600 text-slate-50 bg-sky-600 hover:bg-sky-700 active:bg-sky-600">
-                                {"Submit"}
+                                class="rounded-md px-6 py-1 border border-sky-600 text-white bg-sky-600 hover:bg-sky-700 active:bg-sky-600">
+                                {lang.t("submit")}
                             </button>
                         </div>
                         </>
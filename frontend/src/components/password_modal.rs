@@ -0,0 +1,108 @@
+use crate::{
+    hooks::{use_change_password, use_lang},
+    i18n,
+    states::toast::{ToastAction, ToastContext},
+};
+use shared::user::ChangePasswordRequest;
+use web_sys::HtmlInputElement;
+use yew::{
+    function_component, html, use_context, use_state, AttrValue, Callback, Html, NodeRef,
+    Properties,
+};
+
+// ========================// PasswordModal //======================== //
+
+#[derive(Default)]
+struct InputForm {
+    old_password: NodeRef,
+    new_password: NodeRef,
+    confirm: NodeRef,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct PasswordModalProps {
+    pub onclose: Callback<()>,
+}
+
+#[function_component]
+pub fn PasswordModal(props: &PasswordModalProps) -> Html {
+    let lang = use_lang();
+    let toast = use_context::<ToastContext>().unwrap();
+    let change = use_change_password(props.onclose.clone());
+    let input = use_state(InputForm::default);
+
+    let oncancel = {
+        let onclose = props.onclose.clone();
+        move |_| onclose.emit(())
+    };
+
+    let onsubmit = {
+        let input = input.clone();
+        let change = change.clone();
+
+        move |_| {
+            let value = |node: &NodeRef| {
+                node.cast::<HtmlInputElement>()
+                    .map(|x| x.value())
+                    .unwrap_or_default()
+            };
+
+            let old_password = value(&input.old_password);
+            let new_password = value(&input.new_password);
+            let confirm = value(&input.confirm);
+
+            if old_password.is_empty() || new_password.is_empty() {
+                return;
+            }
+            if new_password != confirm {
+                let msg = AttrValue::from(i18n::t("password-mismatch"));
+                toast.dispatch(ToastAction::Error(msg));
+                return;
+            }
+
+            let req = ChangePasswordRequest {
+                old_password,
+                new_password,
+            };
+            change.run(req);
+        }
+    };
+
+    html! {
+        <div class="relative z-20" role="dialog">
+            <div class="fixed inset-0 bg-slate-400 bg-opacity-75 transition-opacity"></div>
+            <div class="fixed inset-0 p-5 flex min-h-full items-center justify-center">
+                <form onsubmit={onsubmit} action="javascript:void(0);"
+                    class="w-96 transform overflow-hidden rounded-lg shadow-xl transition-all">
+                    <div class="bg-surface px-6 py-5 space-y-4">
+                        <h3 class="text-center text-base font-semibold leading-6 text-slate-900">
+                            {lang.t("password-title")}
+                        </h3>
+
+                        <div>
+                            <label for="input-old-passwd" class="block mb-1 text-sm font-semibold text-slate-500">{lang.t("password-current")}</label>
+                            <input id="input-old-passwd" type="password" minlength="6" maxlength="50" ref={&input.old_password}
+                                class="w-full px-3 rounded-md border-0 py-1.5 text-slate-700 shadow-sm ring-1 ring-inset ring-slate-300 invalid:ring-rose-500 focus:ring-2 focus:ring-sky-600" />
+                        </div>
+                        <div>
+                            <label for="input-new-passwd" class="block mb-1 text-sm font-semibold text-slate-500">{lang.t("password-new")}</label>
+                            <input id="input-new-passwd" type="password" minlength="6" maxlength="50" ref={&input.new_password}
+                                class="w-full px-3 rounded-md border-0 py-1.5 text-slate-700 shadow-sm ring-1 ring-inset ring-slate-300 invalid:ring-rose-500 focus:ring-2 focus:ring-sky-600" />
+                        </div>
+                        <div>
+                            <label for="input-confirm-passwd" class="block mb-1 text-sm font-semibold text-slate-500">{lang.t("password-confirm")}</label>
+                            <input id="input-confirm-passwd" type="password" minlength="6" maxlength="50" ref={&input.confirm}
+                                class="w-full px-3 rounded-md border-0 py-1.5 text-slate-700 shadow-sm ring-1 ring-inset ring-slate-300 invalid:ring-rose-500 focus:ring-2 focus:ring-sky-600" />
+                        </div>
+                    </div>
+                    <div class="bg-slate-100 px-5 py-3 flex items-center justify-end space-x-4">
+                        <button type="button" onclick={oncancel}
+                            class="rounded-md bg-surface px-3 py-2 text-sm font-semibold text-slate-900 shadow-sm ring-1 ring-slate-300 hover:bg-slate-200">{lang.t("cancel")}</button>
+                        <button type="submit" disabled={change.loading()}
+                            class="rounded-md bg-sky-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-sky-500 disabled:cursor-wait disabled:bg-cyan-700">{lang.t("submit")}</button>
+                    </div>
+                </form>
+            </div>
+        </div>
+    }
+}
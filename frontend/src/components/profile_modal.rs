@@ -0,0 +1,114 @@
+use super::common::Avatar;
+use crate::{
+    hooks::{use_lang, use_update_profile},
+    states::auth::AuthContext,
+};
+use shared::user::UpdateUserRequest;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::{function_component, html, use_context, use_state, Callback, Html, NodeRef, Properties};
+
+// ========================// ProfileModal //======================== //
+
+#[derive(Default)]
+struct InputForm {
+    nickname: NodeRef,
+    avatar: NodeRef,
+    bio: NodeRef,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ProfileModalProps {
+    pub onclose: Callback<()>,
+}
+
+#[function_component]
+pub fn ProfileModal(props: &ProfileModalProps) -> Html {
+    let lang = use_lang();
+    let auth = use_context::<AuthContext>().unwrap();
+    let update = use_update_profile(props.onclose.clone());
+    let input = use_state(InputForm::default);
+
+    let oncancel = {
+        let onclose = props.onclose.clone();
+        move |_| onclose.emit(())
+    };
+
+    let onsubmit = {
+        let auth = auth.clone();
+        let input = input.clone();
+        let update = update.clone();
+        let onclose = props.onclose.clone();
+
+        move |_| {
+            // a field left empty is cleared, the nickname cannot be
+            let changed = |value: Option<String>, old: &String| value.filter(|x| x != old);
+
+            let nickname = input.nickname.cast::<HtmlInputElement>().map(|x| x.value());
+            let avatar = input.avatar.cast::<HtmlInputElement>().map(|x| x.value());
+            let bio = input.bio.cast::<HtmlTextAreaElement>().map(|x| x.value());
+
+            let req = UpdateUserRequest {
+                user_id: auth.user.id,
+                username: None,
+                password: None,
+                nickname: changed(nickname, &auth.user.nickname),
+                avatar: changed(avatar, &auth.user.avatar),
+                bio: changed(bio, &auth.user.bio),
+                role: None,
+                deleted: None,
+            };
+
+            if req.nickname.is_none() && req.avatar.is_none() && req.bio.is_none() {
+                onclose.emit(());
+                return;
+            }
+            update.run(req);
+        }
+    };
+
+    html! {
+        <div class="relative z-20" role="dialog">
+            <div class="fixed inset-0 bg-slate-400 bg-opacity-75 transition-opacity"></div>
+            <div class="fixed inset-0 p-5 flex min-h-full items-center justify-center">
+                <form onsubmit={onsubmit} action="javascript:void(0);"
+                    class="w-96 transform overflow-hidden rounded-lg shadow-xl transition-all">
+                    <div class="bg-surface px-6 py-5 space-y-4">
+                        <div class="flex flex-col items-center space-y-2">
+                            <div class="shrink-0 rounded-full">
+                                <Avatar image={auth.user.avatar.clone()} classes="h-16 w-16" />
+                            </div>
+                            <h3 class="text-base font-semibold leading-6 text-slate-900">
+                                {auth.user.username.clone()}
+                            </h3>
+                        </div>
+
+                        <div>
+                            <label for="input-nickname" class="block mb-1 text-sm font-semibold text-slate-500">{lang.t("profile-nickname")}</label>
+                            <input id="input-nickname" type="text" minlength="2" maxlength="50" ref={&input.nickname}
+                                value={auth.user.nickname.clone()}
+                                class="w-full px-3 rounded-md border-0 py-1.5 text-slate-700 shadow-sm ring-1 ring-inset ring-slate-300 invalid:ring-rose-500 focus:ring-2 focus:ring-sky-600" />
+                        </div>
+                        <div>
+                            <label for="input-avatar" class="block mb-1 text-sm font-semibold text-slate-500">{lang.t("profile-avatar")}</label>
+                            <input id="input-avatar" type="text" maxlength="200" ref={&input.avatar}
+                                value={auth.user.avatar.clone()}
+                                class="w-full px-3 rounded-md border-0 py-1.5 text-slate-700 shadow-sm ring-1 ring-inset ring-slate-300 focus:ring-2 focus:ring-sky-600" />
+                        </div>
+                        <div>
+                            <label for="input-bio" class="block mb-1 text-sm font-semibold text-slate-500">{lang.t("profile-bio")}</label>
+                            <textarea id="input-bio" rows="3" maxlength="200" ref={&input.bio}
+                                value={auth.user.bio.clone()}
+                                class="w-full px-3 rounded-md border-0 py-1.5 resize-none text-slate-700 shadow-sm ring-1 ring-inset ring-slate-300 focus:ring-2 focus:ring-sky-600" />
+                        </div>
+                    </div>
+                    <div class="bg-slate-100 px-5 py-3 flex items-center justify-end space-x-4">
+                        <button type="button" onclick={oncancel}
+                            class="rounded-md bg-surface px-3 py-2 text-sm font-semibold text-slate-900 shadow-sm ring-1 ring-slate-300 hover:bg-slate-200">{lang.t("cancel")}</button>
+                        <button type="submit" disabled={update.loading()}
+                            class="rounded-md bg-sky-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-sky-500 disabled:cursor-wait disabled:bg-cyan-700">{lang.t("save")}</button>
+                    </div>
+                </form>
+            </div>
+        </div>
+    }
+}
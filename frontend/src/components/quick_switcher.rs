@@ -0,0 +1,211 @@
+use super::common::Avatar;
+use crate::{
+    hooks::{use_click_away, use_lang},
+    states::chat::ChatState,
+};
+use gloo_events::{EventListener, EventListenerOptions};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::{
+    classes, function_component, html, use_effect_with_deps, use_node_ref, use_state_eq, Callback,
+    Html, Properties,
+};
+
+// ========================// QuickSwitcher //======================== //
+
+#[derive(PartialEq, Properties)]
+pub struct QuickSwitcherProps {
+    pub chat: ChatState,
+}
+
+/// Overlay to jump to a room or a private chat by typing, opened by Ctrl+K
+#[function_component]
+pub fn QuickSwitcher(props: &QuickSwitcherProps) -> Html {
+    let lang = use_lang();
+    let open = use_state_eq(|| false);
+    let query = use_state_eq(String::default);
+    let selected = use_state_eq(|| 0_usize);
+    let input_ref = use_node_ref();
+    let panel_ref = use_node_ref();
+
+    // Ctrl+K, or Cmd+K on a Mac, from anywhere in the chat page
+    {
+        let open = open.clone();
+        let query = query.clone();
+        let selected = selected.clone();
+        use_effect_with_deps(
+            move |_| {
+                let document = web_sys::window().unwrap_throw().document().unwrap_throw();
+                let options = EventListenerOptions::enable_prevent_default();
+                let listener =
+                    EventListener::new_with_options(&document, "keydown", options, move |e| {
+                        let event = e.dyn_ref::<KeyboardEvent>().unwrap_throw();
+                        if (event.ctrl_key() || event.meta_key())
+                            && event.key().eq_ignore_ascii_case("k")
+                        {
+                            event.prevent_default();
+                            query.set(String::default());
+                            selected.set(0);
+                            open.set(true);
+                        }
+                    });
+
+                move || drop(listener)
+            },
+            (),
+        );
+    }
+
+    {
+        let input_ref = input_ref.clone();
+        use_effect_with_deps(
+            move |open| {
+                if let Some(input) = input_ref.cast::<HtmlInputElement>().filter(|_| *open) {
+                    let _ = input.focus();
+                }
+            },
+            *open,
+        );
+    }
+
+    let onclose = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(false))
+    };
+    use_click_away(panel_ref.clone(), onclose.clone());
+
+    let items = if *open {
+        props.chat.quick_switch(&query)
+    } else {
+        Vec::new()
+    };
+    let selected_idx = (*selected).min(items.len().saturating_sub(1));
+
+    let onpick = {
+        let chat = props.chat.clone();
+        let onclose = onclose.clone();
+
+        Callback::from(move |room_id: i64| {
+            chat.switch_room(room_id);
+            onclose.emit(());
+        })
+    };
+
+    let oninput = {
+        let query = query.clone();
+        let selected = selected.clone();
+        let input_ref = input_ref.clone();
+
+        move |_| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                query.set(input.value());
+                selected.set(0);
+            }
+        }
+    };
+
+    // the arrows move through the results, Enter opens one and Escape closes
+    let onkeydown = {
+        let onpick = onpick.clone();
+        let picked = items.get(selected_idx).map(|x| x.room_id);
+        let count = items.len();
+        let selected = selected.clone();
+
+        move |event: KeyboardEvent| {
+            if event.is_composing() {
+                return;
+            }
+            match event.key().as_str() {
+                "ArrowUp" | "ArrowDown" if count > 0 => {
+                    event.prevent_default();
+                    let step = if event.key() == "ArrowUp" {
+                        count - 1
+                    } else {
+                        1
+                    };
+                    selected.set((selected_idx + step) % count);
+                }
+                "Enter" => {
+                    event.prevent_default();
+                    if let Some(room_id) = picked {
+                        onpick.emit(room_id);
+                    }
+                }
+                "Escape" => onclose.emit(()),
+                _ => {}
+            }
+        }
+    };
+
+    if !*open {
+        return html! {};
+    }
+
+    let show_items = if items.is_empty() {
+        html! {
+            <p class="px-4 py-6 text-center text-sm text-slate-400">{lang.t("switcher-nothing")}</p>
+        }
+    } else {
+        let items = items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let onclick = {
+                    let onpick = onpick.clone();
+                    let room_id = item.room_id;
+                    move |_| onpick.emit(room_id)
+                };
+                let onmouseenter = {
+                    let selected = selected.clone();
+                    move |_| selected.set(idx)
+                };
+                let (cls, hint_cls) = if idx == selected_idx {
+                    ("bg-sky-600 text-white", "text-sky-100")
+                } else {
+                    ("text-slate-700", "text-slate-400")
+                };
+
+                html! {
+                    <li {onclick} {onmouseenter}
+                        class={classes!("px-4", "py-2", "flex", "items-center", "space-x-3", "cursor-pointer", cls)}>
+                        <div class="shrink-0 rounded-full">
+                            <Avatar image={item.cover.clone()} classes="h-8 w-8" />
+                        </div>
+                        <div class="min-w-0 grow">
+                            <p class="truncate text-sm font-semibold">{item.name.clone()}</p>
+                            if !item.hint.is_empty() {
+                                <p class={classes!("truncate", "text-xs", hint_cls)}>{item.hint.clone()}</p>
+                            }
+                        </div>
+                        if item.unreads > 0 {
+                            <p class="shrink-0 p-0.5 rounded-full px-1.5 text-xs text-white bg-rose-500">
+                                {item.unreads}
+                            </p>
+                        }
+                    </li>
+                }
+            })
+            .collect::<Html>();
+
+        html! {
+            <ul class="py-1 max-h-96 overflow-y-auto">
+                {items}
+            </ul>
+        }
+    };
+
+    html! {
+        <div class="relative z-20" role="dialog">
+            <div class="fixed inset-0 bg-slate-400 bg-opacity-75 transition-opacity"></div>
+            <div class="fixed inset-0 p-5 pt-[15vh] flex justify-center items-start">
+                <div ref={panel_ref} class="w-full max-w-md overflow-hidden rounded-lg shadow-xl bg-surface">
+                    <input ref={input_ref} type="text" spellcheck="false" value={(*query).clone()}
+                        {oninput} {onkeydown} placeholder={lang.t("switcher-placeholder")}
+                        class="w-full px-4 py-3 border-b bg-transparent outline-none text-slate-700 placeholder:text-slate-400" />
+                    {show_items}
+                    <p class="px-4 py-2 border-t text-xs text-slate-400">{lang.t("switcher-keys")}</p>
+                </div>
+            </div>
+        </div>
+    }
+}
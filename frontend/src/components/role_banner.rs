@@ -14,7 +14,7 @@ pub fn RoleBanner(props: &RoleBannerProps) -> Html {
         "user" => "bg-sky-500",
         _ => "bg-slate-500",
     };
-    let cls = "rounded-lg px-2 py-1 capitalize text-slate-50 text-xs font-semibold".to_owned();
+    let cls = "rounded-lg px-2 py-1 capitalize text-white text-xs font-semibold".to_owned();
 
     html! {
         <span class={classes!(cls, color)}>
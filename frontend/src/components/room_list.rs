@@ -1,8 +1,11 @@
 use super::{common::Avatar, search_box::SearchBox};
-use crate::states::{chat::ChatState, room::RoomListTrigger, types::RoomItem};
+use crate::{
+    hooks::{use_lang, use_subscribe},
+    states::{chat::ChatState, settings::SettingsContext, store::Topic, types::RoomItem},
+};
 use yew::{
-    classes, function_component, html, use_memo, use_state, Callback, Html, Properties,
-    UseStateHandle,
+    classes, function_component, html, use_context, use_memo, use_state, Callback, Html,
+    Properties, UseStateHandle,
 };
 
 // ========================// RoomList //======================== //
@@ -10,21 +13,24 @@ use yew::{
 #[derive(PartialEq, Properties)]
 pub struct RoomListProps {
     pub chat: ChatState,
-    pub trigger: RoomListTrigger,
     pub curr_room_id: UseStateHandle<i64>,
     pub nav_room: Callback<i64>,
 }
 
 #[function_component]
 pub fn RoomList(props: &RoomListProps) -> Html {
-    let rooms = use_memo(|_| props.chat.get_rooms(), props.trigger.clone());
+    let lang = use_lang();
+    let version = use_subscribe(props.chat.store(), vec![Topic::Rooms]);
+    // the times of the latest messages are written in the language
+    let rooms = use_memo(|_| props.chat.get_rooms(), (version, lang));
+    let settings = use_context::<SettingsContext>().unwrap();
 
     let onnewroom = {
         let nav_room = props.nav_room.clone();
         move |_| nav_room.emit(-1)
     };
 
-    let searched = use_state(|| Vec::new());
+    let searched = use_state(Vec::new);
     let searching = use_state(|| false);
 
     let onenter = {
@@ -45,7 +51,7 @@ pub fn RoomList(props: &RoomListProps) -> Html {
             .map(|item| {
                 html! {
                     <RoomEntry key={item.id} room_item={item.clone()} nav_room={props.nav_room.clone()}
-                        is_selected={*props.curr_room_id == item.id} />
+                        is_selected={*props.curr_room_id == item.id} muted={settings.is_muted(item.id)} />
                 }
             })
             .collect::<Html>()
@@ -55,7 +61,7 @@ pub fn RoomList(props: &RoomListProps) -> Html {
             .map(|item| {
                 html! {
                     <RoomEntry key={item.id} room_item={item.clone()} nav_room={props.nav_room.clone()}
-                        is_selected={*props.curr_room_id == item.id} />
+                        is_selected={*props.curr_room_id == item.id} muted={settings.is_muted(item.id)} />
                 }
             })
             .collect::<Html>()
@@ -65,18 +71,18 @@ pub fn RoomList(props: &RoomListProps) -> Html {
         <>
         <div class="px-3 my-2 w-full">
             <SearchBox searching={searching.clone()} onenter={onenter.clone()}
-                placeholder={"Search rooms..."}/>
+                placeholder={lang.t("rooms-search")}/>
         </div>
 
         <div class="my-3 px-5 flex justify-between items-center">
             <p class="text-slate-500 font-semibold text-xs">
-                {"CHAT ROOMS"}
+                {lang.t("rooms-title")}
                 <span class="ml-2 text-sky-700 font-bold">
                     {if *searching {(*searched).len()} else {rooms.len()}}
                 </span>
             </p>
             <div onclick={onnewroom}
-                class="rounded-full p-2 cursor-pointer text-slate-500 hover:text-sky-600 hover:bg-slate-300 active:text-sky-500">
+                class="rounded-full p-3 md:p-2 cursor-pointer text-slate-500 hover:text-sky-600 hover:bg-slate-300 active:text-sky-500">
                 <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
                     stroke="currentColor" class="w-5 h-5">
                     <path stroke-linecap="round" stroke-linejoin="round" d="M12 4.5v15m7.5-7.5h-15" />
@@ -98,30 +104,50 @@ pub fn RoomList(props: &RoomListProps) -> Html {
 struct RoomEntryProps {
     room_item: RoomItem,
     is_selected: bool,
+    // counted apart from the unread badge of the page
+    muted: bool,
     nav_room: Callback<i64>,
 }
 
 #[function_component]
 fn RoomEntry(props: &RoomEntryProps) -> Html {
+    let lang = use_lang();
     let onclick = {
         let room_id = props.room_item.id;
         let nav_room = props.nav_room.clone();
         move |_| nav_room.emit(room_id)
     };
 
+    let muted = props.muted;
     let show_unreads = move |unreads: i64| {
-        let cls = "shrink-0 p-0.5 rounded-full px-1.5 bg-rose-500 text-xs text-slate-50".to_owned();
+        let cls = "shrink-0 p-0.5 rounded-full px-1.5 text-xs text-white".to_owned();
+        let color = if muted { "bg-slate-400" } else { "bg-rose-500" };
 
         html! {
-            <p class={classes!(cls, (unreads - 1).is_negative().then(|| "invisible"))} >
+            <p class={classes!(cls, color, (unreads - 1).is_negative().then_some("invisible"))} >
                 {unreads}
             </p>
         }
     };
 
+    // a room with a draft shows it instead of its latest message
+    let show_latest = match &props.room_item.draft {
+        Some(draft) => html! {
+            <p class="p-0.5 truncate text-xs text-slate-400 group-hover:text-white font-medium">
+                <span class="text-rose-500 group-hover:text-white">{lang.t("room-draft")}{" "}</span>
+                {draft.clone()}
+            </p>
+        },
+        None => html! {
+            <p class="p-0.5 truncate text-xs text-slate-400 group-hover:text-white font-medium">
+                {props.room_item.latest_msg.clone()}
+            </p>
+        },
+    };
+
     if props.is_selected {
         html! {
-            <li class="group bg-sky-600 p-3 flex w-full items-start space-x-3 cursor-pointer">
+            <li {onclick} class="group bg-sky-600 p-3 flex w-full items-start space-x-3 cursor-pointer">
                 <div class="shrink-0 rounded-full group-hover:ring-2 group-hover:ring-sky-100">
                     <Avatar image={props.room_item.cover.clone()} classes="h-10 w-10" />
                 </div>
@@ -130,12 +156,12 @@ fn RoomEntry(props: &RoomEntryProps) -> Html {
                         <p class="truncate text-sm font-semibold text-white">
                             {props.room_item.name.clone()}
                         </p>
-                        <p class="shrink-0 text-xs text-slate-50">
+                        <p class="shrink-0 text-xs text-white">
                             {props.room_item.latest_time.clone()}
                         </p>
                     </div>
                     <div class="mt-1 flex items-center justify-between space-x-2">
-                        <p class="p-0.5 truncate text-xs text-slate-50 font-medium">
+                        <p class="p-0.5 truncate text-xs text-white font-medium">
                             {props.room_item.latest_msg.clone()}
                         </p>
 
@@ -155,14 +181,12 @@ fn RoomEntry(props: &RoomEntryProps) -> Html {
                         <p class="truncate text-sm font-semibold text-slate-600 group-hover:text-white">
                             {props.room_item.name.clone()}
                         </p>
-                        <p class="shrink-0 text-xs text-slate-400 group-hover:text-slate-50">
+                        <p class="shrink-0 text-xs text-slate-400 group-hover:text-white">
                             {props.room_item.latest_time.clone()}
                         </p>
                     </div>
                     <div class="mt-1 flex items-center justify-between space-x-2">
-                        <p class="p-0.5 truncate text-xs text-slate-400 group-hover:text-slate-50 font-medium">
-                            {props.room_item.latest_msg.clone()}
-                        </p>
+                        {show_latest}
 
                         {show_unreads(props.room_item.unreads)}
                     </div>
@@ -1,14 +1,25 @@
 use super::{
-    common::Avatar, member_list::MemberList, message_list::MessageList, new_room::NewRoom,
+    common::{Avatar, BackButton},
+    member_list::MemberList,
+    message_list::MessageList,
+    new_room::NewRoom,
     room_list::RoomList,
 };
-use crate::states::{
-    chat::ChatState,
-    room::{CurrRoomTrigger, RoomPageTrigger},
-    types::CurrRoomItem,
+use crate::{
+    hooks::{use_lang, use_pane, use_subscribe},
+    pages::{route::Route, Pane},
+    sound::{self, Cue},
+    states::{
+        chat::ChatState,
+        settings::{SettingsAction, SettingsContext},
+        store::Topic,
+        types::CurrRoomItem,
+    },
 };
+use web_sys::HtmlSelectElement;
 use yew::{
-    function_component, html, use_state_eq, Callback, Children, Html, Properties, UseStateHandle,
+    classes, function_component, html, use_context, use_effect_with_deps, use_state_eq, Callback,
+    Children, Event, Html, Properties, TargetCast, UseStateHandle,
 };
 
 // ========================// RoomPage //======================== //
@@ -16,58 +27,97 @@ use yew::{
 #[derive(PartialEq, Properties)]
 pub struct RoomPageProps {
     pub chat: ChatState,
-    pub trigger: RoomPageTrigger,
+    // room of the route, `-1` for the new room form
+    pub room_id: i64,
 }
 
 #[function_component]
 pub fn RoomPage(props: &RoomPageProps) -> Html {
     let open_desc = use_state_eq(|| false);
+    let pane = use_pane();
+    // the details are a pane of their own on a narrow viewport
     let nav_desc = {
         let open_desc = open_desc.clone();
-        Callback::from(move |open: bool| open_desc.set(open))
+        let pane = pane.clone();
+        Callback::from(move |open: bool| {
+            open_desc.set(open);
+            if open {
+                pane.open(Pane::Details);
+            } else if pane.pane() == Pane::Details {
+                pane.back();
+            }
+        })
     };
 
-    let curr_room_id = use_state_eq(|| props.chat.curr_room());
+    let curr_room_id = use_state_eq(|| props.room_id);
+    // follow a room opened by a link or the history of the browser
+    {
+        let chat = props.chat.clone();
+        let open_desc = open_desc.clone();
+        let curr_room_id = curr_room_id.clone();
+        use_effect_with_deps(
+            move |room_id| {
+                if chat.curr_room() != *room_id {
+                    chat.set_curr_room(*room_id);
+                }
+                if *curr_room_id != *room_id {
+                    open_desc.set(false);
+                    curr_room_id.set(*room_id);
+                }
+                || ()
+            },
+            props.room_id,
+        );
+    }
     let nav_room = {
         let chat = props.chat.clone();
         let open_desc = open_desc.clone();
         let curr_room_id = curr_room_id.clone();
+        let pane = pane.clone();
 
         Callback::from(move |room_id: i64| {
             chat.set_curr_room(room_id);
             open_desc.set(false);
             curr_room_id.set(room_id);
+            pane.goto(Route::room(room_id), Pane::Conversation);
         })
     };
+    let show_desc = if pane.is_mobile() {
+        pane.pane() == Pane::Details
+    } else {
+        *open_desc
+    };
 
     html! {
         <>
         // middle room list
-        <div class="shrink-0 h-full w-64 pt-3 border-r bg-slate-200 flex flex-col">
-            <RoomList chat={props.chat.clone()} trigger={props.trigger.to_rooms()}
+        <div class={classes!("shrink-0", "h-full", "w-full", "md:w-64", "pt-3", "border-r", "bg-slate-200", "flex-col", pane.display(Pane::List))}>
+            <RoomList chat={props.chat.clone()}
                 curr_room_id={curr_room_id.clone()} nav_room={nav_room.clone()}/>
         </div>
 
         // right chat box
+        <div class={classes!("grow", "min-w-0", "h-full", pane.display(Pane::Conversation))}>
         {
             if *curr_room_id == -1 {
                 html! {
-                    <NewRoom chat={props.chat.clone()} trigger={props.trigger.to_new()} />
+                    <NewRoom chat={props.chat.clone()} />
                 }
             } else {
                 html! {
-                    <CurrRoom chat={props.chat.clone()} trigger={props.trigger.to_curr()} curr_room_id={curr_room_id.clone()}
-                        open_desc={open_desc.clone()} nav_desc={nav_desc.clone()}>
-                        <MessageList chat={props.chat.clone()} trigger={props.trigger.to_messages()} curr_room_id={curr_room_id.clone()} />
+                    <CurrRoom chat={props.chat.clone()} curr_room_id={curr_room_id.clone()}
+                        {show_desc} nav_desc={nav_desc.clone()}>
+                        <MessageList chat={props.chat.clone()} curr_room_id={curr_room_id.clone()} />
                     </CurrRoom>
                 }
             }
         }
+        </div>
 
         // room members
-        <RoomDesc chat={props.chat.clone()} trigger={props.trigger.to_curr()} curr_room_id={curr_room_id.clone()}
-            open_desc={open_desc.clone()} nav_desc={nav_desc.clone()}>
-            <MemberList chat={props.chat.clone()} trigger={props.trigger.to_members()}
+        <RoomDesc chat={props.chat.clone()} curr_room_id={curr_room_id.clone()}
+            {show_desc} nav_desc={nav_desc.clone()}>
+            <MemberList chat={props.chat.clone()}
                 curr_room_id={curr_room_id.clone()}
                 nav_room={nav_room.clone()} />
         </RoomDesc>
@@ -81,21 +131,40 @@ pub fn RoomPage(props: &RoomPageProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct CurrRoomProps {
     chat: ChatState,
-    trigger: CurrRoomTrigger,
     curr_room_id: UseStateHandle<i64>,
-    open_desc: UseStateHandle<bool>,
+    show_desc: bool,
     nav_desc: Callback<bool>,
     children: Children,
 }
 
 #[function_component]
 fn CurrRoom(props: &CurrRoomProps) -> Html {
+    use_subscribe(props.chat.store(), vec![Topic::Room(*props.curr_room_id)]);
     let room = props.chat.get_curr_room(*props.curr_room_id);
+    let lang = use_lang();
+
+    let settings = use_context::<SettingsContext>().unwrap();
+    let muted = settings.is_muted(*props.curr_room_id);
+    let onmute = {
+        let room_id = *props.curr_room_id;
+        move |_| settings.dispatch(SettingsAction::Mute(room_id, !muted))
+    };
+    let show_mute = if muted {
+        html! {
+            <path stroke-linecap="round" stroke-linejoin="round"
+                d="M9.143 17.082a24.248 24.248 0 003.844.148m-3.844-.148a23.856 23.856 0 01-5.455-1.31 8.964 8.964 0 002.3-5.542m3.155 6.852a3 3 0 005.667 1.97m1.965-2.277L21 21m-4.225-4.225a23.81 23.81 0 003.536-1.003A8.967 8.967 0 0118 9.75V9A6 6 0 006.53 6.53m10.245 10.245L6.53 6.53M3 3l3.53 3.53" />
+        }
+    } else {
+        html! {
+            <path stroke-linecap="round" stroke-linejoin="round"
+                d="M14.857 17.082a23.848 23.848 0 005.454-1.31A8.967 8.967 0 0118 9.75v-.7V9A6 6 0 006 9v.75a8.967 8.967 0 01-2.312 6.022c1.733.64 3.56 1.085 5.455 1.31m5.714 0a24.255 24.255 0 01-5.714 0m5.714 0a3 3 0 11-5.714 0" />
+        }
+    };
 
     let ontoggle = {
-        let open_desc = props.open_desc.clone();
+        let show_desc = props.show_desc;
         let nav_desc = props.nav_desc.clone();
-        move |_| nav_desc.emit(!*open_desc)
+        move |_| nav_desc.emit(!show_desc)
     };
 
     if let Some(room) = room {
@@ -103,16 +172,28 @@ fn CurrRoom(props: &CurrRoomProps) -> Html {
             <div class="grow h-full py-1 bg-slate-100 flex flex-col">
                 // room header
                 <div class="shrink-0 h-14 border-b px-4 flex items-center justify-between">
-                    <div class="shrink-0 rounded-full">
-                        <Avatar image={room.cover()} classes={"h-9 w-9"} />
+                    <div class="flex items-center">
+                        <BackButton classes="-ml-3 mr-1" />
+                        <div class="shrink-0 rounded-full">
+                            <Avatar image={room.cover()} classes={"h-9 w-9"} />
+                        </div>
                     </div>
-                    <p class="text-slate-700 font-semibold">{room.name()}</p>
-                    <div onclick={ontoggle}
-                        class="w-9 h-9 rounded-full cursor-pointer text-slate-500 hover:text-sky-600 active:text-sky-500 hover:bg-slate-200 flex items-center justify-center">
-                        <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
-                            stroke="currentColor" class="w-5 h-5">
-                            <path stroke-linecap="round" stroke-linejoin="round" d="M3.75 6.75h16.5M3.75 12h16.5m-16.5 5.25h16.5" />
-                        </svg>
+                    <p class="min-w-0 px-2 truncate text-slate-700 font-semibold">{room.name()}</p>
+                    <div class="flex items-center space-x-1">
+                        <div onclick={onmute} title={lang.t(if muted {"room-unmute"} else {"room-mute"})}
+                            class="w-11 h-11 md:w-9 md:h-9 rounded-full cursor-pointer text-slate-500 hover:text-sky-600 active:text-sky-500 hover:bg-slate-200 flex items-center justify-center">
+                            <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
+                                stroke="currentColor" class="w-5 h-5">
+                                {show_mute}
+                            </svg>
+                        </div>
+                        <div onclick={ontoggle}
+                            class="w-11 h-11 md:w-9 md:h-9 rounded-full cursor-pointer text-slate-500 hover:text-sky-600 active:text-sky-500 hover:bg-slate-200 flex items-center justify-center">
+                            <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
+                                stroke="currentColor" class="w-5 h-5">
+                                <path stroke-linecap="round" stroke-linejoin="round" d="M3.75 6.75h16.5M3.75 12h16.5m-16.5 5.25h16.5" />
+                            </svg>
+                        </div>
                     </div>
                 </div>
 
@@ -137,15 +218,17 @@ fn CurrRoom(props: &CurrRoomProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct RoomDescProps {
     chat: ChatState,
-    trigger: CurrRoomTrigger,
     curr_room_id: UseStateHandle<i64>,
-    open_desc: UseStateHandle<bool>,
+    show_desc: bool,
     nav_desc: Callback<bool>,
     children: Children,
 }
 
 #[function_component]
 fn RoomDesc(props: &RoomDescProps) -> Html {
+    use_subscribe(props.chat.store(), vec![Topic::Room(*props.curr_room_id)]);
+    // the description of a personal room is translated as it is read
+    use_lang();
     let room_type = props.chat.get_curr_room(*props.curr_room_id);
 
     let onclose = {
@@ -199,10 +282,11 @@ fn RoomDesc(props: &RoomDescProps) -> Html {
     };
 
     html! {
-        <div hidden={!*props.open_desc} class="shrink-0 h-full w-56 border-l bg-slate-50">
+        <div hidden={!props.show_desc} class="shrink-0 h-full w-full md:w-56 border-l bg-slate-50">
             <div class="h-full flex flex-col">
-                <div class="shrink-0 h-8 px-2 flex items-center justify-end">
-                    <div onclick={onclose} class="cursor-pointer text-slate-500 hover:text-slate-800 active:text-slate-500">
+                <div class="shrink-0 h-12 md:h-8 px-2 flex items-center justify-between">
+                    <RoomSound curr_room_id={*props.curr_room_id} />
+                    <div onclick={onclose} class="p-3 -mr-2 md:p-0 md:mr-0 cursor-pointer text-slate-500 hover:text-slate-800 active:text-slate-500">
                         <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="1.5"
                             stroke="currentColor" class="w-4 h-4">
                             <path stroke-linecap="round" stroke-linejoin="round" d="M6 18L18 6M6 6l12 12" />
@@ -222,3 +306,57 @@ fn RoomDesc(props: &RoomDescProps) -> Html {
 
     }
 }
+
+// ========================// RoomSound //======================== //
+
+#[derive(PartialEq, Properties)]
+struct RoomSoundProps {
+    curr_room_id: i64,
+}
+
+/// Sound pack of the room, in place of the one of the settings
+#[function_component]
+fn RoomSound(props: &RoomSoundProps) -> Html {
+    let settings = use_context::<SettingsContext>().unwrap();
+    let lang = use_lang();
+    let room_id = props.curr_room_id;
+    let selected = settings.room_sounds.get(&room_id).cloned();
+
+    let onchange = {
+        let settings = settings.clone();
+        move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                let pack = Some(select.value()).filter(|x| !x.is_empty());
+                if let Some(pack) = &pack {
+                    sound::preview(pack, Cue::Message, settings.volume);
+                }
+                settings.dispatch(SettingsAction::RoomSound(room_id, pack));
+            }
+        }
+    };
+
+    let default_label = sound::PACKS
+        .iter()
+        .find(|(x, _)| *x == settings.sound_pack)
+        .map_or(sound::OFF_LABEL, |(_, label)| label);
+    let options = sound::PACKS
+        .iter()
+        .chain([(sound::OFF, sound::OFF_LABEL)].iter())
+        .map(|(pack, label)| {
+            html! {
+                <option value={*pack} selected={selected.as_deref() == Some(*pack)}>{lang.t(label)}</option>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <label class="flex items-center space-x-1 text-xs text-slate-500">
+            <span>{lang.t("room-sound")}</span>
+            <select {onchange}
+                class="rounded-md py-0.5 px-1 text-xs text-slate-600 bg-surface ring-1 ring-slate-300 focus:outline-none focus:ring-sky-600">
+                <option value="" selected={selected.is_none()}>{lang.tr("room-sound-default", &[("pack", &lang.t(default_label))])}</option>
+                {options}
+            </select>
+        </label>
+    }
+}
@@ -68,9 +68,9 @@ pub fn SearchBox(props: &SearchBoxProps) -> Html {
                 </svg>
             </div>
             <input type="text" spellcheck="false" ref={input_node_ref} value={(*input_value).clone()} {oninput} {onkeyup}
-                class="w-full rounded-lg text-sm px-8 py-2 border-0 placeholder:text-slate-400 text-slate-800 leading-tight bg-slate-300 focus:bg-white focus:ring-2 focus:ring-sky-600 focus:outline-none"
+                class="w-full rounded-lg text-sm px-8 py-2 border-0 placeholder:text-slate-400 text-slate-800 leading-tight bg-slate-300 focus:bg-surface focus:ring-2 focus:ring-sky-600 focus:outline-none"
                 placeholder={props.placeholder.clone()} />
-            <div onclick={onclear} class={classes!(cls, (*input_value).is_empty().then(|| "invisible"))}>
+            <div onclick={onclear} class={classes!(cls, (*input_value).is_empty().then_some("invisible"))}>
                 <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
                     stroke="currentColor" class="w-4 h-4 text-slate-700">
                     <path stroke-linecap="round" stroke-linejoin="round" d="M6 18L18 6M6 6l12 12" />
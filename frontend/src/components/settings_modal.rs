@@ -0,0 +1,240 @@
+use super::common::LangSelect;
+use crate::{
+    hooks::use_lang,
+    i18n, notify,
+    sound::{self, Cue},
+    states::{
+        settings::{SettingsAction, SettingsContext},
+        theme::{ThemeContext, ThemeMode, ThemeState, BACKGROUNDS},
+        toast::{ToastAction, ToastContext},
+    },
+};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{
+    classes, function_component, html, use_context, AttrValue, Callback, Event, Html, Properties,
+    TargetCast,
+};
+
+// ========================// SettingsModal //======================== //
+
+#[derive(PartialEq, Properties)]
+pub struct SettingsModalProps {
+    pub onclose: Callback<()>,
+}
+
+#[function_component]
+pub fn SettingsModal(props: &SettingsModalProps) -> Html {
+    let lang = use_lang();
+    let settings = use_context::<SettingsContext>().unwrap();
+    let theme = use_context::<ThemeContext>().unwrap();
+    let toast = use_context::<ToastContext>().unwrap();
+
+    let onclose = {
+        let onclose = props.onclose.clone();
+        move |_| onclose.emit(())
+    };
+
+    // the browser is asked for the permission when notifications are turned on
+    let onnotify = {
+        let settings = settings.clone();
+
+        Callback::from(move |enabled: bool| {
+            if !enabled {
+                settings.dispatch(SettingsAction::Notifications(false));
+                return;
+            }
+            let settings = settings.clone();
+            let toast = toast.clone();
+            spawn_local(async move {
+                if notify::request_permission().await {
+                    settings.dispatch(SettingsAction::Notifications(true));
+                } else {
+                    let msg = AttrValue::from(i18n::t("settings-notifications-blocked"));
+                    toast.dispatch(ToastAction::Error(msg));
+                }
+            });
+        })
+    };
+
+    // the new sound is played once picked
+    let onpack = {
+        let settings = settings.clone();
+        move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                sound::preview(&select.value(), Cue::Message, settings.volume);
+                settings.dispatch(SettingsAction::SoundPack(select.value()));
+            }
+        }
+    };
+    let onvolume = {
+        let settings = settings.clone();
+        move |e: Event| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                let volume = input.value().parse::<u8>().unwrap_or(settings.volume);
+                sound::preview(&settings.sound_pack, Cue::Message, volume);
+                settings.dispatch(SettingsAction::Volume(volume));
+            }
+        }
+    };
+
+    let ontheme = {
+        let theme = theme.clone();
+        move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                if let Some(mode) = ThemeMode::from_name(&select.value()) {
+                    theme.set(ThemeState {
+                        mode,
+                        ..(*theme).clone()
+                    });
+                }
+            }
+        }
+    };
+    let onbackground = {
+        let theme = theme.clone();
+        move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                theme.set(ThemeState {
+                    background: select.value(),
+                    ..(*theme).clone()
+                });
+            }
+        }
+    };
+
+    let modes = ThemeMode::ALL
+        .iter()
+        .map(|mode| {
+            html! {
+                <option value={mode.name()} selected={theme.mode == *mode}>
+                    {lang.t(&format!("theme-{}", mode.name()))}
+                </option>
+            }
+        })
+        .collect::<Html>();
+    let backgrounds = BACKGROUNDS
+        .iter()
+        .map(|(name, _)| {
+            html! {
+                <option value={*name} selected={theme.background == *name}>
+                    {lang.t(&format!("background-{}", name))}
+                </option>
+            }
+        })
+        .collect::<Html>();
+
+    let packs = sound::PACKS
+        .iter()
+        .chain([(sound::OFF, sound::OFF_LABEL)].iter())
+        .map(|(pack, label)| {
+            html! {
+                <option value={*pack} selected={settings.sound_pack == *pack}>{lang.t(label)}</option>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="relative z-20" role="dialog">
+            <div class="fixed inset-0 bg-slate-400 bg-opacity-75 transition-opacity"></div>
+            <div class="fixed inset-0 p-5 flex min-h-full items-center justify-center">
+                <div class="w-96 transform overflow-hidden rounded-lg shadow-xl transition-all">
+                    <div class="bg-surface px-6 py-5 space-y-4">
+                        <h3 class="text-center text-base font-semibold leading-6 text-slate-900">
+                            {lang.t("settings")}
+                        </h3>
+
+                        <div class="flex items-center justify-between space-x-4">
+                            <p class="text-sm font-semibold text-slate-600">{lang.t("settings-language")}</p>
+                            <LangSelect classes="shrink-0" />
+                        </div>
+
+                        <div class="flex items-center justify-between space-x-4">
+                            <p class="text-sm font-semibold text-slate-600">{lang.t("settings-theme")}</p>
+                            <select onchange={ontheme}
+                                class="shrink-0 rounded-md py-0.5 px-1 text-sm text-slate-600 bg-surface ring-1 ring-slate-300 focus:outline-none focus:ring-sky-600">
+                                {modes}
+                            </select>
+                        </div>
+
+                        <div class="flex items-center justify-between space-x-4">
+                            <p class="text-sm font-semibold text-slate-600">{lang.t("settings-background")}</p>
+                            <select onchange={onbackground}
+                                class="shrink-0 rounded-md py-0.5 px-1 text-sm text-slate-600 bg-surface ring-1 ring-slate-300 focus:outline-none focus:ring-sky-600">
+                                {backgrounds}
+                            </select>
+                        </div>
+
+                        <SettingSwitch label={lang.t("settings-notifications")}
+                            desc={lang.t("settings-notifications-desc")}
+                            checked={settings.notifications && notify::is_granted()}
+                            disabled={!notify::is_supported()} ontoggle={onnotify} />
+
+                        <div class="flex items-start justify-between space-x-4">
+                            <div>
+                                <p class="text-sm font-semibold text-slate-600">{lang.t("settings-sounds")}</p>
+                                <p class="text-xs text-slate-400">{lang.t("settings-sounds-desc")}</p>
+                            </div>
+                            <select onchange={onpack}
+                                class="shrink-0 rounded-md py-0.5 px-1 text-sm text-slate-600 bg-surface ring-1 ring-slate-300 focus:outline-none focus:ring-sky-600">
+                                {packs}
+                            </select>
+                        </div>
+
+                        <div class="flex items-center justify-between space-x-4">
+                            <p class="text-sm font-semibold text-slate-600">{lang.t("settings-volume")}</p>
+                            <input type="range" min="0" max="100" step="5" value={settings.volume.to_string()}
+                                onchange={onvolume} disabled={settings.sound_pack == sound::OFF}
+                                class="w-40 accent-sky-600 disabled:opacity-50" />
+                        </div>
+                    </div>
+                    <div class="bg-slate-100 px-5 py-3 flex items-center justify-end">
+                        <button type="button" onclick={onclose}
+                            class="rounded-md bg-sky-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-sky-500">{lang.t("done")}</button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// ========================// SettingSwitch //======================== //
+
+#[derive(PartialEq, Properties)]
+struct SettingSwitchProps {
+    label: AttrValue,
+    desc: AttrValue,
+    checked: bool,
+    #[prop_or_default]
+    disabled: bool,
+    ontoggle: Callback<bool>,
+}
+
+#[function_component]
+fn SettingSwitch(props: &SettingSwitchProps) -> Html {
+    let onclick = {
+        let checked = props.checked;
+        let ontoggle = props.ontoggle.clone();
+        move |_| ontoggle.emit(!checked)
+    };
+
+    let (track, knob) = if props.checked {
+        ("bg-sky-600", "translate-x-5")
+    } else {
+        ("bg-slate-300", "translate-x-0")
+    };
+
+    html! {
+        <div class="flex items-start justify-between space-x-4">
+            <div>
+                <p class="text-sm font-semibold text-slate-600">{props.label.clone()}</p>
+                <p class="text-xs text-slate-400">{props.desc.clone()}</p>
+            </div>
+            <button type="button" role="switch" aria-checked={props.checked.to_string()}
+                {onclick} disabled={props.disabled}
+                class={classes!("shrink-0", "w-11", "h-6", "p-0.5", "rounded-full", "transition-colors", "disabled:opacity-50", track)}>
+                <span class={classes!("block", "w-5", "h-5", "rounded-full", "bg-surface", "shadow", "transition-transform", knob)}></span>
+            </button>
+        </div>
+    }
+}
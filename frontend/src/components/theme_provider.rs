@@ -0,0 +1,44 @@
+use crate::{
+    hooks::{use_local_storage, use_media_query},
+    states::theme::{ThemeContext, ThemeState, THEME},
+};
+use yew::{
+    function_component, html, use_effect_with_deps, Children, ContextProvider, Html, Properties,
+};
+
+const PREFERS_DARK: &str = "(prefers-color-scheme: dark)";
+
+// ========================// ThemeProvider //======================== //
+
+#[derive(PartialEq, Properties)]
+pub struct ThemeProviderProps {
+    pub children: Children,
+}
+
+/// Provide the theme and paint the page with its palette
+#[function_component]
+pub fn ThemeProvider(props: &ThemeProviderProps) -> Html {
+    let theme = use_local_storage::<ThemeState>(THEME);
+    // the system theme follows the browser while the page is open
+    let prefers_dark = use_media_query(PREFERS_DARK);
+
+    // the palette of the classes is picked in input.css by this attribute
+    use_effect_with_deps(
+        |(mode, prefers_dark)| {
+            let root = web_sys::window()
+                .and_then(|x| x.document())
+                .and_then(|x| x.document_element());
+            if let Some(root) = root {
+                let _ = root.set_attribute("data-theme", mode.resolve(*prefers_dark));
+            }
+            || ()
+        },
+        (theme.mode, prefers_dark),
+    );
+
+    html! {
+        <ContextProvider<ThemeContext> context={theme}>
+            {props.children.clone()}
+        </ContextProvider<ThemeContext>>
+    }
+}
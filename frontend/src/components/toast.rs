@@ -33,7 +33,7 @@ pub fn Toast() -> Html {
     html! {
         <div hidden={!toast.open} class="fixed inset-x-0 top-0 z-10">
             <div class={format!("mt-8 mx-auto max-w-md w-fit h-fit px-4 py-3 rounded-md shadow-lg flex space-x-4 {}", toast.color)}>
-                <div class="text-slate-50">
+                <div class="text-white">
                     <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="1.5"
                         stroke="currentColor" class="w-5 h-5">
                         <path stroke-linecap="round" stroke-linejoin="round"
@@ -43,7 +43,7 @@ pub fn Toast() -> Html {
                 <span class="text-sm text-white truncate min-w-0">
                     {toast.message.clone()}
                 </span>
-                <div onclick={onclose} class="text-slate-50 hover:text-slate-800 cursor-pointer">
+                <div onclick={onclose} class="text-white hover:text-white/60 cursor-pointer">
                     <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
                         stroke="currentColor" class="w-5 h-5">
                         <path stroke-linecap="round" stroke-linejoin="round" d="M6 18L18 6M6 6l12 12" />
@@ -0,0 +1,404 @@
+use super::{common::Avatar, role_banner::RoleBanner};
+use crate::{
+    api::config::ApiConfig,
+    hooks::{use_lang, use_request, UseRequestHandle},
+    states::{auth::AuthContext, types::time_date},
+};
+use shared::user::{
+    CreateUserRequest, CreateUserResponse, DeleteUsersRequest, DeleteUsersResponse,
+    ListUsersRequest, ListUsersResponse, UpdateUserRequest, UpdateUserResponse, UserInfo,
+};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, use_state, Callback, Html,
+    NodeRef, Properties, TargetCast,
+};
+
+const PAGE_SIZE: i64 = 10;
+
+// ========================// UserTable //======================== //
+
+#[function_component]
+pub fn UserTable() -> Html {
+    let lang = use_lang();
+    let auth = use_context::<AuthContext>().unwrap();
+
+    let list: UseRequestHandle<ListUsersRequest, ListUsersResponse> =
+        use_request(ApiConfig::ListUsers);
+    let create: UseRequestHandle<CreateUserRequest, CreateUserResponse> =
+        use_request(ApiConfig::CreateUser);
+    let update: UseRequestHandle<UpdateUserRequest, UpdateUserResponse> =
+        use_request(ApiConfig::UpdateUser);
+    let delete: UseRequestHandle<DeleteUsersRequest, DeleteUsersResponse> =
+        use_request(ApiConfig::DeleteUsers);
+
+    let page_id = use_state(|| 1_i64);
+    let selected = use_state(Vec::<i64>::new);
+
+    // reload the current page whenever it changes or a modification finishes
+    {
+        let list = list.clone();
+        let selected = selected.clone();
+        let busy = create.loading() || update.loading() || delete.loading();
+
+        use_effect_with_deps(
+            move |&(page_id, busy)| {
+                if !busy {
+                    selected.set(Vec::new());
+                    list.send(ListUsersRequest {
+                        page_id: Some(page_id),
+                        page_size: Some(PAGE_SIZE),
+                    });
+                }
+            },
+            (*page_id, busy),
+        );
+    }
+
+    let total = (*list).as_ref().map(|x| x.total).unwrap_or(0);
+    let pages = ((total + PAGE_SIZE - 1) / PAGE_SIZE).max(1);
+
+    // the last page is gone once its users are deleted
+    {
+        let page_id = page_id.clone();
+        use_effect_with_deps(
+            move |&pages| {
+                if *page_id > pages {
+                    page_id.set(pages);
+                }
+            },
+            pages,
+        );
+    }
+
+    let onprev = {
+        let page_id = page_id.clone();
+        move |_| {
+            if *page_id > 1 {
+                page_id.set(*page_id - 1);
+            }
+        }
+    };
+    let onnext = {
+        let page_id = page_id.clone();
+        move |_| {
+            if *page_id < pages {
+                page_id.set(*page_id + 1);
+            }
+        }
+    };
+
+    let onselect = {
+        let selected = selected.clone();
+        Callback::from(move |id: i64| {
+            let mut new = (*selected).clone();
+            if let Some(idx) = new.iter().position(|&x| x == id) {
+                new.swap_remove(idx);
+            } else {
+                new.push(id);
+            }
+            selected.set(new);
+        })
+    };
+
+    let ondelete_selected = {
+        let delete = delete.clone();
+        let selected = selected.clone();
+        move |_| {
+            let user_ids = (*selected).clone();
+            if !user_ids.is_empty() {
+                delete.send(DeleteUsersRequest { user_ids });
+            }
+        }
+    };
+
+    // users are only marked as deleted, a restore updates them back
+    let ondelete = {
+        let delete = delete.clone();
+        Callback::from(move |user_id: i64| {
+            delete.send(DeleteUsersRequest {
+                user_ids: vec![user_id],
+            })
+        })
+    };
+
+    let onupdate = {
+        let update = update.clone();
+        Callback::from(move |req: UpdateUserRequest| update.send(req))
+    };
+
+    let oncreate = {
+        let create = create.clone();
+        Callback::from(move |req: CreateUserRequest| create.send(req))
+    };
+
+    let show_rows = (*list)
+        .as_ref()
+        .map(|data| {
+            data.users
+                .iter()
+                .map(|user| {
+                    html! {
+                        <UserRow key={user.id} user={user.clone()} is_self={user.id == auth.user.id}
+                            selected={selected.contains(&user.id)} onselect={onselect.clone()}
+                            ondelete={ondelete.clone()} onupdate={onupdate.clone()} />
+                    }
+                })
+                .collect::<Html>()
+        })
+        .unwrap_or_default();
+
+    html! {
+        <div class="rounded-md bg-surface shadow-sm">
+            <div class="px-4 py-3 border-b flex items-center justify-between">
+                <p class="text-slate-500 font-semibold text-xs">
+                    {lang.t("users-title")}
+                    <span class="ml-2 text-sky-700 font-bold">{total}</span>
+                </p>
+                <button type="button" onclick={ondelete_selected} disabled={selected.is_empty()}
+                    class="rounded-md px-2 border border-rose-500 text-sm text-rose-500 hover:bg-rose-500 hover:text-white active:bg-rose-300 disabled:opacity-40 disabled:pointer-events-none">
+                    {lang.t("users-delete-selected")}
+                </button>
+            </div>
+
+            <NewUserForm oncreate={oncreate} loading={create.loading()}
+                created={(*create).as_ref().map(|x| x.user.id)} />
+
+            <table class="w-full text-sm text-left">
+                <thead class="text-xs text-slate-400 uppercase">
+                    <tr class="border-b">
+                        <th class="px-4 py-2"></th>
+                        <th class="px-2 py-2">{lang.t("users-user")}</th>
+                        <th class="px-2 py-2">{lang.t("users-role")}</th>
+                        <th class="px-2 py-2">{lang.t("users-created")}</th>
+                        <th class="px-2 py-2">{lang.t("users-status")}</th>
+                        <th class="px-4 py-2"></th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-slate-100">
+                    {show_rows}
+                </tbody>
+            </table>
+
+            <div class="px-4 py-3 border-t flex items-center justify-end space-x-3 text-sm text-slate-500">
+                <button type="button" onclick={onprev} disabled={*page_id <= 1}
+                    class="rounded-md px-2 border border-slate-300 hover:bg-slate-200 disabled:opacity-40 disabled:pointer-events-none">
+                    {lang.t("users-prev")}
+                </button>
+                <span>{lang.tr("users-page", &[("page", &page_id.to_string()), ("pages", &pages.to_string())])}</span>
+                <button type="button" onclick={onnext} disabled={*page_id >= pages}
+                    class="rounded-md px-2 border border-slate-300 hover:bg-slate-200 disabled:opacity-40 disabled:pointer-events-none">
+                    {lang.t("users-next")}
+                </button>
+            </div>
+        </div>
+    }
+}
+
+// ========================// UserRow //======================== //
+
+#[derive(PartialEq, Properties)]
+struct UserRowProps {
+    user: UserInfo,
+    is_self: bool,
+    selected: bool,
+    onselect: Callback<i64>,
+    ondelete: Callback<i64>,
+    onupdate: Callback<UpdateUserRequest>,
+}
+
+#[function_component]
+fn UserRow(props: &UserRowProps) -> Html {
+    let lang = use_lang();
+    let user_id = props.user.id;
+
+    let update_req = move |role: Option<String>, deleted: Option<bool>| UpdateUserRequest {
+        user_id,
+        username: None,
+        password: None,
+        nickname: None,
+        avatar: None,
+        bio: None,
+        role,
+        deleted,
+    };
+
+    let onclick = {
+        let onselect = props.onselect.clone();
+        move |_| onselect.emit(user_id)
+    };
+
+    let onchange_role = {
+        let onupdate = props.onupdate.clone();
+        move |e: yew::Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                onupdate.emit(update_req(Some(select.value()), None));
+            }
+        }
+    };
+
+    let ondelete = {
+        let ondelete = props.ondelete.clone();
+        move |_| ondelete.emit(user_id)
+    };
+
+    let onrestore = {
+        let onupdate = props.onupdate.clone();
+        move |_| onupdate.emit(update_req(None, Some(false)))
+    };
+
+    let show_role = if props.is_self {
+        html! { <RoleBanner role={props.user.role.clone()} /> }
+    } else {
+        html! {
+            <select onchange={onchange_role} disabled={props.user.deleted}
+                class="rounded-md py-0.5 px-1 text-sm text-slate-600 bg-surface ring-1 ring-slate-300 focus:outline-none focus:ring-sky-600">
+                <option value="user" selected={props.user.role == "user"}>{lang.t("role-user")}</option>
+                <option value="admin" selected={props.user.role == "admin"}>{lang.t("role-admin")}</option>
+            </select>
+        }
+    };
+
+    let show_op = if props.is_self {
+        html! {}
+    } else if props.user.deleted {
+        html! {
+            <button type="button" onclick={onrestore}
+                class="rounded-md px-2 border border-sky-600 text-sky-600 hover:bg-sky-600 hover:text-white active:bg-sky-400">
+                {lang.t("users-restore")}
+            </button>
+        }
+    } else {
+        html! {
+            <button type="button" onclick={ondelete}
+                class="rounded-md px-2 border border-rose-500 text-rose-500 hover:bg-rose-500 hover:text-white active:bg-rose-300">
+                {lang.t("delete")}
+            </button>
+        }
+    };
+
+    html! {
+        <tr class="hover:bg-slate-50">
+            <td class="px-4 py-2">
+                <input type="checkbox" checked={props.selected} {onclick}
+                    disabled={props.is_self || props.user.deleted} class="w-4 h-4" />
+            </td>
+            <td class="px-2 py-2">
+                <div class="flex items-center space-x-2">
+                    <div class="shrink-0 rounded-full">
+                        <Avatar image={props.user.avatar.clone()} classes="h-7 w-7" />
+                    </div>
+                    <div class="min-w-0">
+                        <p class="truncate font-semibold text-slate-600">{props.user.username.clone()}</p>
+                        <p class="truncate text-xs text-slate-400">{props.user.nickname.clone()}</p>
+                    </div>
+                </div>
+            </td>
+            <td class="px-2 py-2">{show_role}</td>
+            <td class="px-2 py-2 text-slate-500">{time_date(&props.user.create_at)}</td>
+            <td class="px-2 py-2">
+                {
+                    if props.user.deleted {
+                        html! { <span class="text-xs font-semibold text-rose-500">{lang.t("users-deleted")}</span> }
+                    } else {
+                        html! { <span class="text-xs font-semibold text-emerald-600">{lang.t("users-active")}</span> }
+                    }
+                }
+            </td>
+            <td class="px-4 py-2 text-right">{show_op}</td>
+        </tr>
+    }
+}
+
+// ========================// NewUserForm //======================== //
+
+#[derive(Default)]
+struct InputForm {
+    username: NodeRef,
+    password: NodeRef,
+    role: NodeRef,
+}
+
+#[derive(PartialEq, Properties)]
+struct NewUserFormProps {
+    oncreate: Callback<CreateUserRequest>,
+    loading: bool,
+    // id of the user created last
+    created: Option<i64>,
+}
+
+#[function_component]
+fn NewUserForm(props: &NewUserFormProps) -> Html {
+    let lang = use_lang();
+    let input = use_state(InputForm::default);
+
+    // the inputs are kept until the user is created, for another try
+    {
+        let input = input.clone();
+        use_effect_with_deps(
+            move |created| {
+                if created.is_some() {
+                    if let Some(x) = input.username.cast::<HtmlInputElement>() {
+                        x.set_value("");
+                    }
+                    if let Some(x) = input.password.cast::<HtmlInputElement>() {
+                        x.set_value("");
+                    }
+                }
+            },
+            props.created,
+        );
+    }
+
+    let onsubmit = {
+        let input = input.clone();
+        let oncreate = props.oncreate.clone();
+
+        move |_| {
+            let username = input
+                .username
+                .cast::<HtmlInputElement>()
+                .map(|x| x.value())
+                .unwrap_or_default();
+            let password = input
+                .password
+                .cast::<HtmlInputElement>()
+                .map(|x| x.value())
+                .unwrap_or_default();
+            let role = input
+                .role
+                .cast::<HtmlSelectElement>()
+                .map(|x| x.value())
+                .unwrap_or_else(|| "user".to_owned());
+
+            if username.is_empty() || password.is_empty() {
+                return;
+            }
+
+            oncreate.emit(CreateUserRequest {
+                username,
+                password,
+                role,
+            });
+        }
+    };
+
+    html! {
+        <form onsubmit={onsubmit} action="javascript:void(0);"
+            class="px-4 py-3 border-b flex items-center space-x-3">
+            <input type="text" minlength="2" maxlength="50" placeholder={lang.t("username")} ref={&input.username}
+                class="w-full py-1 px-2 rounded-md bg-surface text-sm text-slate-700 border-2 border-slate-300 focus:outline-none invalid:border-rose-500 focus:border-sky-600" />
+            <input type="password" minlength="6" maxlength="50" placeholder={lang.t("password")} ref={&input.password}
+                class="w-full py-1 px-2 rounded-md bg-surface text-sm text-slate-700 border-2 border-slate-300 focus:outline-none invalid:border-rose-500 focus:border-sky-600" />
+            <select ref={&input.role}
+                class="rounded-md py-1 px-1 text-sm text-slate-600 bg-surface border-2 border-slate-300 focus:outline-none focus:border-sky-600">
+                <option value="user" selected={true}>{lang.t("role-user")}</option>
+                <option value="admin">{lang.t("role-admin")}</option>
+            </select>
+            <button type="submit" disabled={props.loading}
+                class="shrink-0 rounded-md px-4 py-1 border border-sky-600 bg-sky-600 text-sm text-white hover:bg-sky-700 active:bg-sky-600 disabled:cursor-wait">
+                {lang.t("users-create")}
+            </button>
+        </form>
+    }
+}
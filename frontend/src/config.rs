@@ -0,0 +1,97 @@
+use gloo_net::http::Request;
+use serde::Deserialize;
+use std::cell::OnceCell;
+use wasm_bindgen::UnwrapThrowExt;
+
+// id of the inline `<script type="application/json">` holding the config
+const INLINE_CONFIG: &str = "app-config";
+// fetched when there is no inline config
+const CONFIG_URL: &str = "/config.json";
+
+thread_local! {
+    static CONFIG: OnceCell<RuntimeConfig> = const { OnceCell::new() };
+}
+
+// ========================// RuntimeConfig //======================== //
+
+/// Endpoints of the server, read at boot so that one build can be
+/// deployed behind any host or reverse proxy
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct RuntimeConfig {
+    // prefix of the api urls, e.g. `https://chat.example.com`, same origin if empty
+    pub api_base: String,
+    // url of the websocket, derived from the page location if empty
+    pub ws_url: String,
+}
+
+impl RuntimeConfig {
+    /// Read the inline config from `index.html`, or fetch it from the server
+    pub async fn load() -> Self {
+        if let Some(config) = Self::from_inline() {
+            return config;
+        }
+
+        // hosts serving the app for any path answer with `index.html`, which is no config
+        match Request::get(CONFIG_URL).send().await {
+            Ok(resp) if resp.ok() && is_json(resp.headers().get("content-type")) => {
+                resp.json::<Self>().await.unwrap_or_else(|e| {
+                    gloo_console::error!("invalid config:", e.to_string());
+                    Self::default()
+                })
+            }
+            _ => Self::default(),
+        }
+    }
+
+    fn from_inline() -> Option<Self> {
+        let text = web_sys::window()?
+            .document()?
+            .get_element_by_id(INLINE_CONFIG)?
+            .text_content()?;
+
+        serde_json::from_str(&text)
+            .map_err(|e| gloo_console::error!("invalid inline config:", e.to_string()))
+            .ok()
+    }
+
+    /// Make the config available to the rest of the app, only the first call counts
+    pub fn install(self) {
+        CONFIG.with(|x| {
+            let _ = x.set(self);
+        });
+    }
+}
+
+fn is_json(content_type: Option<String>) -> bool {
+    content_type.is_some_and(|x| x.contains("json"))
+}
+
+// ========================// Endpoints //======================== //
+
+/// Full url of an api path
+pub fn api_url(path: &str) -> String {
+    CONFIG.with(|x| match x.get() {
+        Some(config) => format!("{}{}", config.api_base.trim_end_matches('/'), path),
+        None => path.to_owned(),
+    })
+}
+
+/// Url of the websocket, `ws:` or `wss:` following the page protocol by default
+pub fn ws_url() -> String {
+    let configured = CONFIG.with(|x| x.get().map(|config| config.ws_url.clone()));
+
+    match configured {
+        Some(url) if !url.is_empty() => url,
+        _ => {
+            let location = web_sys::window().unwrap_throw().location();
+            let host = location.host().unwrap_throw();
+            let protocol = location.protocol().unwrap_throw();
+            let ws_protocol = match protocol.as_str() {
+                "https:" => "wss:",
+                _ => "ws:",
+            };
+            format!("{}//{}/ws", ws_protocol, host)
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use super::{spawn_write, with_store};
+use crate::states::{
+    types::{FriendAttr, MemberAttr, MessageAttr, Relation, RoomAttr},
+    websocket::WebSocketState,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use web_sys::IdbTransactionMode;
+
+pub(super) const STORE: &str = "cache";
+
+// only the most recent messages of each room are kept
+const CACHE_MESSAGES: usize = 50;
+
+// ========================// CachedState //======================== //
+
+/// Last known rooms and friends of a user, shown until the server snapshot arrives
+#[derive(Deserialize, Serialize)]
+pub struct CachedState {
+    pub rooms: Vec<CachedRoom>,
+    pub friends: Vec<CachedFriend>,
+}
+
+impl CachedState {
+    fn from_state(state: &WebSocketState) -> Self {
+        Self {
+            rooms: state.rooms.values().map(CachedRoom::from).collect(),
+            friends: state.friends.values().map(CachedFriend::from).collect(),
+        }
+    }
+}
+
+// ---------------- CachedRoom ---------------- //
+#[derive(Deserialize, Serialize)]
+pub struct CachedRoom {
+    pub id: i64,
+    pub name: String,
+    pub cover: String,
+    pub category: String,
+    pub unreads: i64,
+    pub members: Vec<CachedMember>,
+    pub messages: Vec<CachedMessage>,
+}
+
+impl From<&RoomAttr> for CachedRoom {
+    fn from(room: &RoomAttr) -> Self {
+        let skip = room.messages.len().saturating_sub(CACHE_MESSAGES);
+        Self {
+            id: room.id,
+            name: room.name.to_string(),
+            cover: room.cover.to_string(),
+            category: room.category.to_string(),
+            unreads: room.unreads,
+            members: room.members.iter().map(CachedMember::from).collect(),
+            messages: room
+                .messages
+                .iter()
+                .skip(skip)
+                .map(CachedMessage::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<CachedRoom> for RoomAttr {
+    fn from(room: CachedRoom) -> Self {
+        Self {
+            id: room.id,
+            name: room.name.into(),
+            cover: room.cover.into(),
+            category: room.category.into(),
+            unreads: room.unreads,
+            first_unread: None,
+            members: room.members.into_iter().map(MemberAttr::from).collect(),
+            messages: room.messages.into_iter().map(MessageAttr::from).collect(),
+        }
+    }
+}
+
+// ---------------- CachedMember ---------------- //
+#[derive(Deserialize, Serialize)]
+pub struct CachedMember {
+    pub id: i64,
+    pub name: String,
+    pub avatar: String,
+    pub rank: String,
+}
+
+impl From<&MemberAttr> for CachedMember {
+    fn from(member: &MemberAttr) -> Self {
+        Self {
+            id: member.id,
+            name: member.name.to_string(),
+            avatar: member.avatar.to_string(),
+            rank: member.rank.to_string(),
+        }
+    }
+}
+
+impl From<CachedMember> for MemberAttr {
+    fn from(member: CachedMember) -> Self {
+        Self {
+            id: member.id,
+            name: member.name.into(),
+            avatar: member.avatar.into(),
+            rank: member.rank.into(),
+        }
+    }
+}
+
+// ---------------- CachedMessage ---------------- //
+#[derive(Deserialize, Serialize)]
+pub struct CachedMessage {
+    pub id: i64,
+    pub sid: i64,
+    pub name: String,
+    pub avatar: String,
+    pub content: String,
+    pub kind: String,
+    pub send_at: DateTime<Utc>,
+}
+
+impl From<&MessageAttr> for CachedMessage {
+    fn from(message: &MessageAttr) -> Self {
+        Self {
+            id: message.id,
+            sid: message.sid,
+            name: message.name.to_string(),
+            avatar: message.avatar.to_string(),
+            content: message.content.to_string(),
+            kind: message.kind.to_string(),
+            send_at: message.send_at,
+        }
+    }
+}
+
+impl From<CachedMessage> for MessageAttr {
+    fn from(message: CachedMessage) -> Self {
+        Self {
+            id: message.id,
+            sid: message.sid,
+            name: message.name.into(),
+            avatar: message.avatar.into(),
+            content: message.content.into(),
+            kind: message.kind.into(),
+            send_at: message.send_at,
+        }
+    }
+}
+
+// ---------------- CachedFriend ---------------- //
+#[derive(Deserialize, Serialize)]
+pub struct CachedFriend {
+    pub id: i64,
+    pub username: String,
+    pub nickname: String,
+    pub avatar: String,
+    pub bio: String,
+    pub relation: Relation,
+}
+
+impl From<&FriendAttr> for CachedFriend {
+    fn from(friend: &FriendAttr) -> Self {
+        Self {
+            id: friend.id,
+            username: friend.username.to_string(),
+            nickname: friend.nickname.to_string(),
+            avatar: friend.avatar.to_string(),
+            bio: friend.bio.to_string(),
+            relation: friend.relation.clone(),
+        }
+    }
+}
+
+impl From<CachedFriend> for FriendAttr {
+    fn from(friend: CachedFriend) -> Self {
+        Self {
+            id: friend.id,
+            username: friend.username.into(),
+            nickname: friend.nickname.into(),
+            avatar: friend.avatar.into(),
+            bio: friend.bio.into(),
+            relation: friend.relation,
+        }
+    }
+}
+
+// ========================// Cache //======================== //
+
+/// Replace the cached state of the user
+pub fn save(user_id: i64, state: &WebSocketState) {
+    let key = JsValue::from(user_id as f64);
+    let value = match serde_json::to_string(&CachedState::from_state(state)) {
+        Ok(value) => JsValue::from_str(&value),
+        Err(_) => return,
+    };
+
+    spawn_write(async move {
+        with_store(STORE, IdbTransactionMode::Readwrite, |store| {
+            store.put_with_key(&value, &key)
+        })
+        .await
+    });
+}
+
+/// Cached state of the user, if any
+pub async fn load(user_id: i64) -> Result<Option<CachedState>, JsValue> {
+    let key = JsValue::from(user_id as f64);
+    let value = with_store(STORE, IdbTransactionMode::Readonly, |store| store.get(&key)).await?;
+
+    Ok(value
+        .as_string()
+        .and_then(|x| serde_json::from_str::<CachedState>(&x).ok()))
+}
+
+/// Forget the cached state of the user, others signed in on the browser keep theirs
+pub fn clear(user_id: i64) {
+    let key = JsValue::from(user_id as f64);
+    spawn_write(async move {
+        with_store(STORE, IdbTransactionMode::Readwrite, |store| {
+            store.delete(&key)
+        })
+        .await
+    });
+}
@@ -0,0 +1,85 @@
+pub mod cache;
+pub mod outbox;
+
+use js_sys::Promise;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+const DB_NAME: &str = "litchi";
+const DB_VERSION: u32 = 2;
+
+// every object store of the database, created when missing on upgrade
+const STORES: [&str; 2] = [outbox::STORE, cache::STORE];
+
+// ========================// Database //======================== //
+
+/// Open the database, creating the object stores on first use
+async fn open() -> Result<IdbDatabase, JsValue> {
+    let factory = web_sys::window()
+        .ok_or("no window")?
+        .indexed_db()?
+        .ok_or("IndexedDB is not supported")?;
+
+    let req = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+
+    let onupgrade = {
+        let req = req.clone();
+        Closure::once(move |_: web_sys::Event| {
+            if let Ok(db) = req.result().map(IdbDatabase::unchecked_from_js) {
+                let names = db.object_store_names();
+                for store in STORES {
+                    if !names.contains(store) {
+                        let _ = db.create_object_store(store);
+                    }
+                }
+            }
+        })
+    };
+    req.set_onupgradeneeded(Some(onupgrade.as_ref().unchecked_ref()));
+
+    let db = request(&req).await;
+    req.set_onupgradeneeded(None);
+    db.map(IdbDatabase::unchecked_from_js)
+}
+
+/// Wait for a request to complete and return its result
+async fn request(req: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        req.set_onsuccess(Some(&resolve));
+        req.set_onerror(Some(&reject));
+    });
+    let done = JsFuture::from(promise).await;
+
+    req.set_onsuccess(None);
+    req.set_onerror(None);
+    done.and_then(|_| req.result())
+}
+
+/// Run a single request against one object store
+async fn with_store<F>(store: &str, mode: IdbTransactionMode, f: F) -> Result<JsValue, JsValue>
+where
+    F: FnOnce(&IdbObjectStore) -> Result<IdbRequest, JsValue>,
+{
+    let db = open().await?;
+    let req = db
+        .transaction_with_str_and_mode(store, mode)?
+        .object_store(store)
+        .and_then(|x| f(&x))?;
+
+    let result = request(&req).await;
+    db.close();
+    result
+}
+
+/// Run a write in the background, storage is best effort
+fn spawn_write<F>(write: F)
+where
+    F: std::future::Future<Output = Result<JsValue, JsValue>> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(e) = write.await {
+            gloo_console::error!("failed to write to IndexedDB:", e);
+        }
+    });
+}
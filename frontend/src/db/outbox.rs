@@ -0,0 +1,76 @@
+use super::{spawn_write, with_store};
+use chrono::{DateTime, Utc};
+use js_sys::Array;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use wasm_bindgen::JsValue;
+use web_sys::IdbTransactionMode;
+
+pub(super) const STORE: &str = "outbox";
+
+// ========================// OutboxItem //======================== //
+
+/// Message not acknowledged by the server, kept across reloads
+#[derive(Deserialize, Serialize)]
+pub struct OutboxItem {
+    pub user_id: i64,
+    pub room_id: i64,
+    pub nonce: Uuid,
+    pub content: String,
+    pub kind: String,
+    pub create_at: DateTime<Utc>,
+}
+
+fn key(user_id: i64, room_id: i64, nonce: &Uuid) -> JsValue {
+    Array::of3(
+        &JsValue::from(user_id as f64),
+        &JsValue::from(room_id as f64),
+        &JsValue::from_str(&nonce.to_string()),
+    )
+    .into()
+}
+
+// ========================// Outbox //======================== //
+
+/// Keep a message until the server acknowledges it
+pub fn save(item: &OutboxItem) {
+    let key = key(item.user_id, item.room_id, &item.nonce);
+    let value = match serde_json::to_string(item) {
+        Ok(value) => JsValue::from_str(&value),
+        Err(_) => return,
+    };
+
+    spawn_write(async move {
+        with_store(STORE, IdbTransactionMode::Readwrite, |store| {
+            store.put_with_key(&value, &key)
+        })
+        .await
+    });
+}
+
+/// Forget a message acknowledged by the server or discarded by the user
+pub fn remove(user_id: i64, room_id: i64, nonce: &Uuid) {
+    let key = key(user_id, room_id, nonce);
+
+    spawn_write(async move {
+        with_store(STORE, IdbTransactionMode::Readwrite, |store| {
+            store.delete(&key)
+        })
+        .await
+    });
+}
+
+/// Messages of the user left in the outbox, oldest first
+pub async fn load(user_id: i64) -> Result<Vec<OutboxItem>, JsValue> {
+    let values = with_store(STORE, IdbTransactionMode::Readonly, |store| store.get_all()).await?;
+
+    let mut items: Vec<OutboxItem> = Array::from(&values)
+        .iter()
+        .filter_map(|x| x.as_string())
+        .filter_map(|x| serde_json::from_str::<OutboxItem>(&x).ok())
+        .filter(|x| x.user_id == user_id)
+        .collect();
+
+    items.sort_by_key(|x| x.create_at);
+    Ok(items)
+}
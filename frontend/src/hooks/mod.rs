@@ -1,17 +1,33 @@
 mod use_auto_login;
+mod use_change_password;
 mod use_click_away;
+mod use_lang;
 mod use_local_storage;
 mod use_login;
 mod use_logout;
+mod use_media_query;
+mod use_pane;
+mod use_reauth;
 mod use_register;
 mod use_request;
+mod use_subscribe;
+mod use_token_refresh;
+mod use_update_profile;
 mod use_websocket;
 
-pub use use_auto_login::{use_auto_login, UseAutoLoginHandle};
+pub use use_auto_login::use_auto_login;
+pub use use_change_password::use_change_password;
 pub use use_click_away::use_click_away;
+pub use use_lang::use_lang;
 pub use use_local_storage::{use_local_storage, UseLocalStorageHandle};
-pub use use_login::{use_login, UseLoginHandle};
+pub use use_login::use_login;
 pub use use_logout::use_logout;
-pub use use_register::{use_register, UseRegisterHandle};
+pub use use_media_query::use_media_query;
+pub use use_pane::use_pane;
+pub use use_reauth::use_reauth;
+pub use use_register::use_register;
 pub use use_request::{use_request, UseRequestHandle};
+pub use use_subscribe::use_subscribe;
+pub use use_token_refresh::use_token_refresh;
+pub use use_update_profile::use_update_profile;
 pub use use_websocket::{use_websocket, UseWebSocketHandle};
@@ -8,7 +8,7 @@ use crate::{
 use shared::auth::LoginResponse;
 use std::rc::Rc;
 use yew::{hook, use_context, use_state, UseStateHandle};
-use yew_router::prelude::use_navigator;
+use yew_router::prelude::{use_location, use_navigator};
 
 use super::UseLocalStorageHandle;
 
@@ -50,6 +50,8 @@ impl UseAutoLoginHandle {
 pub fn use_auto_login() -> UseAutoLoginHandle {
     let auth = use_context::<AuthContext>().unwrap();
     let navigator = use_navigator().unwrap();
+    // the page the user was sent away from
+    let location = use_location();
     let persist = use_local_storage::<bool>(PERSIST);
     let loading = use_state(|| false);
 
@@ -61,6 +63,7 @@ pub fn use_auto_login() -> UseAutoLoginHandle {
             if *persist {
                 let auth = auth.clone();
                 let navigator = navigator.clone();
+                let location = location.clone();
                 let persist = persist.clone();
                 let loading = loading.clone();
                 let api = AuthApi::new(ApiConfig::AutoLogin);
@@ -69,7 +72,7 @@ pub fn use_auto_login() -> UseAutoLoginHandle {
                     loading.set(true);
                     if let Ok(data) = api.send::<(), LoginResponse>(None).await {
                         auth.dispatch(AuthAction::Set(data));
-                        navigator.push(&Route::Chat);
+                        navigator.push(&Route::destination(location));
                     } else {
                         persist.set(false);
                     }
@@ -0,0 +1,90 @@
+use crate::{
+    api::{config::ApiConfig, private::PrivateApi, ApiError},
+    states::{
+        auth::AuthContext,
+        toast::{ToastAction, ToastContext},
+    },
+    validation::first_validation_error,
+};
+use shared::user::{ChangePasswordRequest, ChangePasswordResponse};
+use std::rc::Rc;
+use validator::Validate;
+use yew::{hook, use_context, use_state, Callback, UseStateHandle};
+
+// ========================// UseChangePasswordHandle //======================== //
+
+pub struct UseChangePasswordHandle {
+    loading: UseStateHandle<bool>,
+    change: Rc<dyn Fn(ChangePasswordRequest)>,
+}
+
+impl Clone for UseChangePasswordHandle {
+    fn clone(&self) -> Self {
+        Self {
+            loading: self.loading.clone(),
+            change: self.change.clone(),
+        }
+    }
+}
+
+impl UseChangePasswordHandle {
+    pub fn loading(&self) -> bool {
+        *self.loading
+    }
+
+    pub fn run(&self, req: ChangePasswordRequest) {
+        (*self.change)(req);
+    }
+}
+
+// ========================// use_change_password //======================== //
+
+#[hook]
+pub fn use_change_password(onsuccess: Callback<()>) -> UseChangePasswordHandle {
+    let auth = use_context::<AuthContext>().unwrap();
+    let toast = use_context::<ToastContext>().unwrap();
+    let loading = use_state(|| false);
+
+    let change = {
+        let loading = loading.clone();
+        let api = PrivateApi::new(ApiConfig::ChangePassword, auth);
+
+        Rc::new(move |payload: ChangePasswordRequest| {
+            if let Err(e) = payload.validate() {
+                let fields = [
+                    ("old_password", "password-current"),
+                    ("new_password", "password-new"),
+                ];
+                let msg = first_validation_error(&e, &fields);
+                toast.dispatch(ToastAction::Error(msg.into()));
+                return;
+            }
+
+            let toast = toast.clone();
+            let loading = loading.clone();
+            let onsuccess = onsuccess.clone();
+            let api = api.clone();
+
+            wasm_bindgen_futures::spawn_local(async move {
+                loading.set(true);
+                match api
+                    .send::<ChangePasswordRequest, ChangePasswordResponse>(&payload)
+                    .await
+                {
+                    Ok(data) => {
+                        toast.dispatch(ToastAction::Info(data.message.into()));
+                        onsuccess.emit(());
+                    }
+                    Err(e) => {
+                        if let ApiError::Toast(msg) = e {
+                            toast.dispatch(ToastAction::Error(msg.into()));
+                        }
+                    }
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    UseChangePasswordHandle { loading, change }
+}
@@ -0,0 +1,13 @@
+use crate::{
+    i18n::{self, Lang},
+    states::lang::LangContext,
+};
+use yew::{hook, use_context};
+
+// ========================// use_lang //======================== //
+
+/// Language of the UI, the component rerenders when it is switched
+#[hook]
+pub fn use_lang() -> Lang {
+    use_context::<LangContext>().map_or_else(i18n::lang, |x| x.lang)
+}
@@ -9,7 +9,7 @@ use crate::{
 use shared::auth::{LoginRequest, LoginResponse};
 use std::rc::Rc;
 use yew::{hook, use_context, use_state, UseStateHandle};
-use yew_router::prelude::use_navigator;
+use yew_router::prelude::{use_location, use_navigator};
 
 // ========================// UseLoginHandle //======================== //
 
@@ -44,6 +44,8 @@ pub fn use_login() -> UseLoginHandle {
     let auth = use_context::<AuthContext>().unwrap();
     let toast = use_context::<ToastContext>().unwrap();
     let navigator = use_navigator().unwrap();
+    // the page the user was sent away from
+    let location = use_location();
     let loading = use_state(|| false);
 
     let login = {
@@ -53,6 +55,7 @@ pub fn use_login() -> UseLoginHandle {
             let auth = auth.clone();
             let toast = toast.clone();
             let navigator = navigator.clone();
+            let location = location.clone();
             let loading = loading.clone();
             let api = AuthApi::new(ApiConfig::Login);
 
@@ -64,7 +67,7 @@ pub fn use_login() -> UseLoginHandle {
                 {
                     Ok(data) => {
                         auth.dispatch(AuthAction::Set(data));
-                        navigator.push(&Route::Chat);
+                        navigator.push(&Route::destination(location));
                     }
                     Err(e) => {
                         if let ApiError::Toast(msg) = e {
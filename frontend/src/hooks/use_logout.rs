@@ -1,12 +1,14 @@
 use crate::{
-    api::{auth::AuthApi, config::ApiConfig},
+    api::{auth::AuthApi, config::ApiConfig, ApiError},
+    db::cache,
+    drafts_key,
     hooks::use_local_storage,
     pages::route::Route,
     states::auth::{AuthAction, AuthContext},
     PERSIST,
 };
 use shared::auth::LogoutResponse;
-use std::rc::Rc;
+use std::{collections::HashMap, rc::Rc};
 use yew::{hook, use_context};
 use yew_router::prelude::use_navigator;
 
@@ -17,16 +19,22 @@ pub fn use_logout() -> Rc<dyn Fn()> {
     let auth = use_context::<AuthContext>().unwrap();
     let navigator = use_navigator().unwrap();
     let persist = use_local_storage::<bool>(PERSIST);
+    let drafts = use_local_storage::<HashMap<i64, String>>(&drafts_key(auth.user.id));
 
     let logout = Rc::new(move || {
         let api = AuthApi::new(ApiConfig::Logout);
         let auth = auth.clone();
         let navigator = navigator.clone();
         let persist = persist.clone();
+        let drafts = drafts.clone();
 
         wasm_bindgen_futures::spawn_local(async move {
-            if let Ok(_) = api.send::<(), LogoutResponse>(None).await {
+            let result = api.send::<(), LogoutResponse>(None).await;
+            // an expired login is left all the same
+            if matches!(result, Ok(_) | Err(ApiError::Expire)) {
                 persist.set(false);
+                drafts.set(HashMap::new());
+                cache::clear(auth.user.id);
                 auth.dispatch(AuthAction::Clear);
                 navigator.replace(&Route::Login);
             }
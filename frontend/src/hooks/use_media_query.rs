@@ -0,0 +1,32 @@
+use gloo_events::EventListener;
+use web_sys::MediaQueryList;
+use yew::{hook, use_effect_with_deps, use_state_eq};
+
+fn media_query(query: &str) -> Option<MediaQueryList> {
+    web_sys::window()?.match_media(query).ok()?
+}
+
+// ========================// use_media_query //======================== //
+
+/// Whether the media query matches, updated as the page changes
+#[hook]
+pub fn use_media_query(query: &'static str) -> bool {
+    let matches = use_state_eq(|| media_query(query).is_some_and(|x| x.matches()));
+
+    {
+        let matches = matches.clone();
+        use_effect_with_deps(
+            move |query| {
+                let listener = media_query(query).map(|list| {
+                    EventListener::new(&list.clone(), "change", move |_| {
+                        matches.set(list.matches())
+                    })
+                });
+                move || drop(listener)
+            },
+            query,
+        );
+    }
+
+    *matches
+}
@@ -0,0 +1,83 @@
+use crate::{
+    hooks::use_media_query,
+    pages::{route::Route, Pane},
+};
+use yew::hook;
+use yew_router::prelude::{use_location, use_navigator, use_route, Navigator};
+
+/// Viewports narrower than this show one pane of the chat page at a time
+pub const MOBILE: &str = "(max-width: 767px)";
+
+// ========================// UsePaneHandle //======================== //
+
+#[derive(Clone)]
+pub struct UsePaneHandle {
+    pane: Pane,
+    mobile: bool,
+    navigator: Navigator,
+    route: Route,
+}
+
+impl UsePaneHandle {
+    pub fn pane(&self) -> Pane {
+        self.pane
+    }
+
+    pub fn is_mobile(&self) -> bool {
+        self.mobile
+    }
+
+    /// Go to a deeper pane on a narrow viewport, the back button of the
+    /// browser returns to the current one
+    pub fn open(&self, pane: Pane) {
+        if self.mobile && pane != self.pane {
+            self.navigator.push_with_state(&self.route, pane);
+        }
+    }
+
+    /// Go to another route of the chat page, shown in the given pane on a
+    /// narrow viewport
+    pub fn goto(&self, route: Route, pane: Pane) {
+        let pane = if self.mobile { pane } else { Pane::List };
+        if route != self.route || pane != self.pane {
+            self.navigator.push_with_state(&route, pane);
+        }
+    }
+
+    /// Return to the pane before
+    pub fn back(&self) {
+        if self.pane != Pane::List {
+            self.navigator.back();
+        }
+    }
+
+    /// Display classes of a pane, the others are hidden on a narrow viewport
+    pub fn display(&self, pane: Pane) -> &'static str {
+        if self.pane == pane {
+            "flex"
+        } else {
+            "hidden md:flex"
+        }
+    }
+}
+
+// ========================// use_pane //======================== //
+
+/// The pane of the chat page shown on a narrow viewport, kept in the state
+/// of the browser history
+#[hook]
+pub fn use_pane() -> UsePaneHandle {
+    let mobile = use_media_query(MOBILE);
+    let navigator = use_navigator().unwrap();
+    let route = use_route::<Route>().unwrap_or(Route::Chat);
+    let pane = use_location()
+        .and_then(|x| x.state::<Pane>())
+        .map_or(Pane::List, |x| *x);
+
+    UsePaneHandle {
+        pane,
+        mobile,
+        navigator,
+        route,
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    api::{auth::AuthApi, config::ApiConfig, renew::resume_requests, ApiError},
+    hooks::use_logout,
+    states::{
+        auth::{AuthAction, AuthContext},
+        toast::{ToastAction, ToastContext},
+    },
+};
+use shared::auth::{LoginRequest, LoginResponse};
+use std::rc::Rc;
+use yew::{hook, use_context, use_state, UseStateHandle};
+
+// ========================// UseReauthHandle //======================== //
+
+pub struct UseReauthHandle {
+    loading: UseStateHandle<bool>,
+    login: Rc<dyn Fn(String)>,
+    cancel: Rc<dyn Fn()>,
+}
+
+impl Clone for UseReauthHandle {
+    fn clone(&self) -> Self {
+        Self {
+            loading: self.loading.clone(),
+            login: self.login.clone(),
+            cancel: self.cancel.clone(),
+        }
+    }
+}
+
+impl UseReauthHandle {
+    pub fn loading(&self) -> bool {
+        *self.loading
+    }
+
+    /// Log in again as the current user with the given password
+    pub fn run(&self, password: String) {
+        (*self.login)(password);
+    }
+
+    /// Give up logging in again and leave the session
+    pub fn cancel(&self) {
+        (*self.cancel)();
+    }
+}
+
+// ========================// use_reauth //======================== //
+
+#[hook]
+pub fn use_reauth() -> UseReauthHandle {
+    let auth = use_context::<AuthContext>().unwrap();
+    let toast = use_context::<ToastContext>().unwrap();
+    let logout = use_logout();
+    let loading = use_state(|| false);
+
+    let login = {
+        let loading = loading.clone();
+
+        Rc::new(move |password: String| {
+            let auth = auth.clone();
+            let toast = toast.clone();
+            let loading = loading.clone();
+            let api = AuthApi::new(ApiConfig::Login);
+            let payload = LoginRequest {
+                username: auth.user.username.clone(),
+                password,
+            };
+
+            wasm_bindgen_futures::spawn_local(async move {
+                loading.set(true);
+                match api
+                    .send::<LoginRequest, LoginResponse>(Some(&payload))
+                    .await
+                {
+                    Ok(data) => {
+                        let token = data.access_token.clone();
+                        auth.dispatch(AuthAction::Set(data));
+                        resume_requests(Some(token));
+                    }
+                    Err(e) => {
+                        if let ApiError::Toast(msg) = e {
+                            toast.dispatch(ToastAction::Error(msg.into()));
+                        }
+                    }
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    let cancel = Rc::new(move || {
+        logout();
+        resume_requests(None);
+    });
+
+    UseReauthHandle {
+        loading,
+        login,
+        cancel,
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     api::{auth::AuthApi, config::ApiConfig, ApiError},
+    i18n,
     pages::route::Route,
     states::toast::{ToastAction, ToastContext},
 };
@@ -58,10 +59,8 @@ pub fn use_register() -> UseRegisterHandle {
                     .await
                 {
                     Ok(data) => {
-                        let msg = AttrValue::from(format!(
-                            "Registration successfully, {}",
-                            data.user.username
-                        ));
+                        let args = [("name", data.user.username.as_str())];
+                        let msg = AttrValue::from(i18n::tr("register-done", &args));
                         toast.dispatch(ToastAction::Info(msg));
                         navigator.push(&Route::Login);
                     }
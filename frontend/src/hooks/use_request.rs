@@ -40,7 +40,7 @@ impl<T, D> Deref for UseRequestHandle<T, D> {
     type Target = Option<D>;
 
     fn deref(&self) -> &Self::Target {
-        &(*self.data)
+        &self.data
     }
 }
 
@@ -0,0 +1,33 @@
+use crate::states::store::{Store, Topic};
+use std::rc::Rc;
+use yew::{hook, use_effect_with_deps, use_force_update, use_mut_ref, Callback};
+
+// ========================// use_subscribe //======================== //
+
+/// Rerender when any of the topics is published, the returned version
+/// changes every time and can be used as a dependency of `use_memo`
+#[hook]
+pub fn use_subscribe(store: Rc<Store>, topics: Vec<Topic>) -> u64 {
+    let version = use_mut_ref(|| 0_u64);
+    let update = use_force_update();
+
+    {
+        let version = version.clone();
+
+        use_effect_with_deps(
+            move |topics| {
+                let callback = Callback::from(move |_| {
+                    *version.borrow_mut() += 1;
+                    update.force_update();
+                });
+                let subscription = store.subscribe(topics, callback);
+
+                move || drop(subscription)
+            },
+            topics,
+        );
+    }
+
+    let version = *version.borrow();
+    version
+}
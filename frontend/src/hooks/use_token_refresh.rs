@@ -0,0 +1,36 @@
+use crate::{api::renew::renew_token, states::auth::AuthContext};
+use chrono::Utc;
+use gloo_timers::callback::Timeout;
+use yew::{hook, use_context, use_effect_with_deps};
+
+// renew the token this long before it expires
+const RENEW_AHEAD: i64 = 60_000;
+// never schedule renewals closer than this, in case of a skewed clock
+const MIN_DELAY: i64 = 10_000;
+
+// ========================// use_token_refresh //======================== //
+
+/// Renew the access token in the background shortly before it expires
+#[hook]
+pub fn use_token_refresh() {
+    let auth = use_context::<AuthContext>().unwrap();
+    let deps = (auth.authorized && !auth.expired, auth.expire_at);
+
+    use_effect_with_deps(
+        move |&(active, expire_at)| {
+            let timeout = active.then(|| {
+                let delay = (expire_at - Utc::now()).num_milliseconds() - RENEW_AHEAD;
+                let delay = delay.clamp(MIN_DELAY, i32::MAX as i64) as u32;
+
+                Timeout::new(delay, move || {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let _ = renew_token(auth).await;
+                    });
+                })
+            });
+
+            move || drop(timeout)
+        },
+        deps,
+    );
+}
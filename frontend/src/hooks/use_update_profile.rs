@@ -0,0 +1,96 @@
+use crate::{
+    api::{config::ApiConfig, private::PrivateApi, ApiError},
+    i18n,
+    states::{
+        auth::{AuthAction, AuthContext},
+        toast::{ToastAction, ToastContext},
+    },
+    validation::first_validation_error,
+};
+use shared::user::{UpdateUserRequest, UpdateUserResponse};
+use std::rc::Rc;
+use validator::Validate;
+use yew::{hook, use_context, use_state, AttrValue, Callback, UseStateHandle};
+
+// ========================// UseUpdateProfileHandle //======================== //
+
+pub struct UseUpdateProfileHandle {
+    loading: UseStateHandle<bool>,
+    update: Rc<dyn Fn(UpdateUserRequest)>,
+}
+
+impl Clone for UseUpdateProfileHandle {
+    fn clone(&self) -> Self {
+        Self {
+            loading: self.loading.clone(),
+            update: self.update.clone(),
+        }
+    }
+}
+
+impl UseUpdateProfileHandle {
+    pub fn loading(&self) -> bool {
+        *self.loading
+    }
+
+    pub fn run(&self, req: UpdateUserRequest) {
+        (*self.update)(req);
+    }
+}
+
+// ========================// use_update_profile //======================== //
+
+#[hook]
+pub fn use_update_profile(onsuccess: Callback<()>) -> UseUpdateProfileHandle {
+    let auth = use_context::<AuthContext>().unwrap();
+    let toast = use_context::<ToastContext>().unwrap();
+    let loading = use_state(|| false);
+
+    let update = {
+        let loading = loading.clone();
+        let api = PrivateApi::new(ApiConfig::UpdateProfile, auth.clone());
+
+        Rc::new(move |payload: UpdateUserRequest| {
+            if let Err(e) = payload.validate() {
+                let fields = [
+                    ("nickname", "profile-nickname"),
+                    ("avatar", "profile-avatar"),
+                    ("bio", "profile-bio"),
+                ];
+                let msg = first_validation_error(&e, &fields);
+                toast.dispatch(ToastAction::Error(msg.into()));
+                return;
+            }
+
+            let auth = auth.clone();
+            let toast = toast.clone();
+            let loading = loading.clone();
+            let onsuccess = onsuccess.clone();
+            let api = api.clone();
+
+            wasm_bindgen_futures::spawn_local(async move {
+                loading.set(true);
+                match api
+                    .send::<UpdateUserRequest, UpdateUserResponse>(&payload)
+                    .await
+                {
+                    Ok(data) => {
+                        auth.dispatch(AuthAction::Update(data.user));
+                        toast.dispatch(ToastAction::Info(AttrValue::from(i18n::t(
+                            "profile-updated",
+                        ))));
+                        onsuccess.emit(());
+                    }
+                    Err(e) => {
+                        if let ApiError::Toast(msg) = e {
+                            toast.dispatch(ToastAction::Error(msg.into()));
+                        }
+                    }
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    UseUpdateProfileHandle { loading, update }
+}
@@ -1,27 +1,59 @@
 use crate::{
-    hooks::use_logout,
+    api::{
+        renew::{relogin, renew_token},
+        ApiError,
+    },
+    config::ws_url,
+    db::{cache, outbox},
+    drafts_key,
+    hooks::{use_local_storage, use_logout},
     states::types::ConvertToMessage,
     states::{
         auth::AuthContext,
-        trigger::{Trigger, TriggerState},
-        websocket::WebSocketState,
+        chat::ChatState,
+        settings::SettingsContext,
+        store::Store,
+        websocket::{ConnStatus, WebSocketState},
     },
 };
-use futures::{channel::mpsc, SinkExt, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    future, SinkExt, StreamExt,
+};
+use gloo_events::EventListener;
 use gloo_net::websocket::{futures::WebSocket, Message, WebSocketError};
-use shared::event::{ClientEvent, ServerEvent};
-use std::{cell::RefCell, rc::Rc};
+use gloo_timers::callback::{Interval, Timeout};
+use shared::{
+    event::{ClientEvent, ServerMessage},
+    session::{AuthenticateRequest, CloseReason, ResumeRequest},
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
 use wasm_bindgen::UnwrapThrowExt;
 use wasm_bindgen_futures::spawn_local;
-use yew::{hook, use_context, use_effect_with_deps, use_mut_ref, use_reducer, use_state};
+use yew::{
+    hook, use_context, use_effect_with_deps, use_memo, use_mut_ref, use_state, use_state_eq,
+    UseStateHandle,
+};
 
-const RECONNECT_LIMIT: u8 = 3;
+// the first retry waits about this long (ms), doubling on every failure
+const BACKOFF_BASE: u32 = 1_000;
+// the longest wait between two retries (ms)
+const BACKOFF_MAX: u32 = 30_000;
+// how often the server is pinged (ms)
+const PING_INTERVAL: u32 = 15_000;
+// the connection is considered dead after this many unanswered pings
+const MISSED_PONGS: u32 = 2;
 
 // ========================// UseWebSocketHandle //======================== //
 
 pub struct UseWebSocketHandle {
     pub inner: Rc<RefCell<WebSocketState>>,
-    pub trigger: Trigger,
+    pub store: Rc<Store>,
+    status: UseStateHandle<ConnStatus>,
 }
 
 impl UseWebSocketHandle {
@@ -29,9 +61,18 @@ impl UseWebSocketHandle {
         self.inner.borrow_mut().tx = tx;
     }
 
-    pub fn handle_event(&mut self, event: ServerEvent) -> Result<(), String> {
-        let mut chat = self.inner.borrow_mut();
-        chat.handle_event(event, self.trigger.clone())
+    pub fn status(&self) -> ConnStatus {
+        *self.status
+    }
+
+    /// Round-trip time of the last ping, in milliseconds
+    pub fn latency(&self) -> Option<i64> {
+        self.inner.borrow().heartbeat.latency
+    }
+
+    /// Server clock minus local clock, in milliseconds
+    pub fn clock_skew(&self) -> i64 {
+        self.inner.borrow().heartbeat.clock_skew
     }
 }
 
@@ -39,112 +80,379 @@ impl Clone for UseWebSocketHandle {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
-            trigger: self.trigger.clone(),
+            store: self.store.clone(),
+            status: self.status.clone(),
         }
     }
 }
 
+// ========================// Backoff //======================== //
+
+#[derive(Default)]
+struct Backoff {
+    // id of the current session, bumped to open a new connection
+    session: u32,
+    // consecutive failed connections, reset once the server answers
+    retries: u32,
+    // the connection is down and waits to be opened again
+    waiting: bool,
+    // pending retry, dropping it cancels the retry
+    timer: Option<Timeout>,
+}
+
+impl Backoff {
+    /// Delay before the next retry, with equal jitter so that clients
+    /// dropped at the same time do not come back all at once
+    fn delay(&self) -> u32 {
+        self.delay_with(js_sys::Math::random())
+    }
+
+    // the delay for a random number between 0 and 1
+    fn delay_with(&self, random: f64) -> u32 {
+        let delay = BACKOFF_BASE
+            .saturating_mul(1 << self.retries.min(15))
+            .min(BACKOFF_MAX);
+        let jitter = random * (delay / 2) as f64;
+        delay / 2 + jitter as u32
+    }
+}
+
+fn is_online() -> bool {
+    web_sys::window()
+        .map(|x| x.navigator().on_line())
+        .unwrap_or(true)
+}
+
 // ========================// use_websocket //======================== //
 
 #[hook]
 pub fn use_websocket() -> UseWebSocketHandle {
     let logout = use_logout();
     let auth = use_context::<AuthContext>().unwrap();
+    let settings = use_context::<SettingsContext>().unwrap();
+
+    let drafts = use_local_storage::<HashMap<i64, String>>(&drafts_key(auth.user.id));
+
+    let inner = use_mut_ref(|| {
+        let mut state = WebSocketState::new(auth.user.id);
+        state.drafts = (*drafts).clone();
+        state
+    });
+    let store = use_memo(|_| Store::default(), ());
+    let status = use_state_eq(|| ConnStatus::Connecting);
+
+    let websocket = UseWebSocketHandle {
+        inner,
+        store,
+        status: status.clone(),
+    };
+
+    let backoff = use_mut_ref(Backoff::default);
+    let session = use_state(|| 0_u32);
+    // the settings of the last render, read by the receiving task
+    let curr_settings = use_mut_ref(|| settings.clone());
+    *curr_settings.borrow_mut() = settings;
+
+    // open a new connection right away
+    let reconnect = {
+        let backoff = backoff.clone();
+        let session = session.clone();
 
-    let inner = use_mut_ref(|| WebSocketState::new(auth.user.id));
-    let trigger = use_reducer(TriggerState::default);
+        Rc::new(move || {
+            let id = {
+                let mut backoff = backoff.borrow_mut();
+                backoff.session += 1;
+                backoff.session
+            };
+            session.set(id);
+        })
+    };
 
-    let websocket = UseWebSocketHandle { inner, trigger };
+    // wait before opening a new connection, or until the network comes back
+    let retry = {
+        let backoff = backoff.clone();
+        let status = status.clone();
+        let reconnect = reconnect.clone();
 
-    // connection count
-    let count = use_state(|| 1_u8);
+        Rc::new(move || {
+            let mut backoff = backoff.borrow_mut();
+            backoff.waiting = true;
 
+            if is_online() {
+                let delay = backoff.delay();
+                backoff.retries += 1;
+
+                let reconnect = reconnect.clone();
+                backoff.timer = Some(Timeout::new(delay, move || reconnect()));
+                status.set(ConnStatus::Reconnecting);
+            } else {
+                backoff.timer = None;
+                status.set(ConnStatus::Offline);
+            }
+        })
+    };
+
+    // follow the network state of the browser
     {
-        let count_ref = count.clone();
-        let mut websocket = websocket.clone();
+        let backoff = backoff.clone();
+        let status = status.clone();
+        let reconnect = reconnect.clone();
 
         use_effect_with_deps(
-            move |&count| {
-                if count >= RECONNECT_LIMIT {
-                    logout();
-                    return;
-                }
+            move |_| {
+                let window = web_sys::window().unwrap_throw();
+
+                let online = EventListener::new(&window, "online", move |_| {
+                    let waiting = {
+                        let mut backoff = backoff.borrow_mut();
+                        backoff.timer = None;
+                        backoff.retries = 0;
+                        backoff.waiting
+                    };
+                    if waiting {
+                        reconnect();
+                    }
+                });
+                let offline = EventListener::new(&window, "offline", move |_| {
+                    status.set(ConnStatus::Offline);
+                });
+
+                move || drop((online, offline))
+            },
+            (),
+        );
+    }
+
+    // show the cached state and bring back the messages left unsent by a previous page
+    {
+        let websocket = websocket.clone();
+        let curr_user = auth.user.id;
+
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let chat = ChatState::from_ws(&websocket);
+
+                    match cache::load(curr_user).await {
+                        Ok(Some(cached)) => chat.restore_cache(cached),
+                        Ok(None) => {}
+                        Err(e) => gloo_console::error!("Failed to read the cache:", e),
+                    }
+
+                    match outbox::load(curr_user).await {
+                        Ok(items) if !items.is_empty() => {
+                            chat.restore_queued(items);
+                        }
+                        Ok(_) => {}
+                        Err(e) => gloo_console::error!("Failed to read the outbox:", e),
+                    }
+                });
+                || ()
+            },
+            (),
+        );
+    }
+
+    {
+        let websocket = websocket.clone();
 
-                // let location = web_sys::window().unwrap_throw().location();
-                // let host = location.host().unwrap_throw();
-                // let protocol = location.protocol().unwrap_throw();
-                // let ws_protocol = match protocol.as_str() {
-                //     "https:" => "wss:",
-                //     _ => "ws:",
-                // };
-                // let ws_addr = format!("{}//{}/ws", ws_protocol, host);
-                // let ws = WebSocket::open(&ws_addr).unwrap_throw();
-                let conn = WebSocket::open("ws://127.0.0.1:8080/ws").unwrap_throw();
-
-                let (mut sender, mut receiver) = conn.split();
+        use_effect_with_deps(
+            move |_| {
+                let conn = WebSocket::open(&ws_url()).unwrap_throw();
+
+                let (mut sender, receiver) = conn.split();
                 let (tx, mut rx) = mpsc::channel::<Message>(256);
                 websocket.set_tx(Some(tx));
 
+                // dropped when this session is replaced or the chat is left
+                let (kill_tx, kill_rx) = oneshot::channel::<()>();
+
+                // ping the server regularly and give up on the connection if it stops answering
+                let (dead_tx, dead_rx) = oneshot::channel::<()>();
+                let dead = Rc::new(Cell::new(false));
+                websocket.inner.borrow_mut().heartbeat.reset();
+
+                let heartbeat = {
+                    let ws = websocket.clone();
+                    let dead = dead.clone();
+                    let mut dead_tx = Some(dead_tx);
+
+                    Interval::new(PING_INTERVAL, move || {
+                        let mut state = ws.inner.borrow_mut();
+                        let req = state.heartbeat.ping();
+
+                        if state.heartbeat.missed >= MISSED_PONGS {
+                            if let Some(dead_tx) = dead_tx.take() {
+                                dead.set(true);
+                                let _ = dead_tx.send(());
+                            }
+                        } else if let Some(tx) = state.tx.as_mut() {
+                            let _ = tx.try_send(ClientEvent::Ping(req).to_msg());
+                        }
+                    })
+                };
+
                 // task for sending message to server
+                let token = auth.token.clone();
+                let last_seq = websocket.inner.borrow().last_seq;
                 spawn_local(async move {
-                    let msg = ClientEvent::Initialization.to_msg();
-                    sender.send(msg).await.unwrap_throw();
+                    let msg = ClientEvent::Authenticate(AuthenticateRequest { token }).to_msg();
+                    if sender.send(msg).await.is_err() {
+                        return;
+                    }
+
+                    // only fetch what has been missed if the state was synced before
+                    let msg = match last_seq {
+                        Some(last_seq) => ClientEvent::Resume(ResumeRequest { last_seq }),
+                        None => ClientEvent::Initialization,
+                    }
+                    .to_msg();
+                    if sender.send(msg).await.is_err() {
+                        return;
+                    }
 
                     while let Some(msg) = rx.next().await {
-                        sender.send(msg).await.unwrap_throw();
+                        if let Err(e) = sender.send(msg).await {
+                            gloo_console::error!("Error while sending message", e.to_string());
+                            break;
+                        }
                     }
                 });
 
                 // task for receiving message and processing events
+                let ws = websocket.clone();
                 spawn_local(async move {
+                    let mut receiver = receiver.take_until(future::select(kill_rx, dead_rx));
+                    let mut connected = false;
+                    let mut accepted = false;
+                    let mut closed = None;
+
                     while let Some(msg) = receiver.next().await {
                         match msg {
                             Ok(Message::Text(msg)) => {
                                 gloo_console::log!(msg);
                             }
                             Ok(Message::Bytes(b)) => {
-                                if let Ok(event) = serde_json::from_slice::<ServerEvent>(&b) {
-                                    if let Err(s) = websocket.handle_event(event) {
-                                        gloo_console::log!(s);
-                                        count_ref.set(RECONNECT_LIMIT);
+                                if !connected {
+                                    connected = true;
+                                    let mut backoff = backoff.borrow_mut();
+                                    backoff.retries = 0;
+                                    backoff.waiting = false;
+                                    status.set(ConnStatus::Connected);
+                                }
+                                if let Ok(msg) = serde_json::from_slice::<ServerMessage>(&b) {
+                                    let settings = curr_settings.borrow().clone();
+                                    let chat = ChatState::from_ws(&websocket);
+                                    if let Err(reason) = chat.handle_message(msg, &settings) {
+                                        closed = Some(reason);
                                         break;
                                     }
+                                    // the session is accepted, resumed or not
+                                    if !accepted {
+                                        accepted = true;
+                                        chat.flush_queued();
+                                    }
                                 }
                             }
-                            Err(e) => match e {
-                                WebSocketError::ConnectionError => {
-                                    gloo_console::error!("Error on connection");
-                                    count_ref.set(*count_ref + 1);
-                                    break;
-                                }
-                                WebSocketError::ConnectionClose(e) => {
-                                    gloo_console::error!(
-                                        "The connection has been closed :",
-                                        e.code
-                                    );
-                                    count_ref.set(RECONNECT_LIMIT);
-                                    break;
-                                }
-                                WebSocketError::MessageSendError(e) => {
-                                    gloo_console::error!(
-                                        "Error while sending message",
-                                        e.to_string()
-                                    );
-                                    count_ref.set(RECONNECT_LIMIT);
-                                    break;
+                            Err(e) => {
+                                match e {
+                                    WebSocketError::ConnectionError => {
+                                        gloo_console::error!("Error on connection");
+                                    }
+                                    WebSocketError::ConnectionClose(e) => {
+                                        gloo_console::error!(
+                                            "The connection has been closed :",
+                                            e.code
+                                        );
+                                    }
+                                    WebSocketError::MessageSendError(e) => {
+                                        gloo_console::error!(
+                                            "Error while sending message",
+                                            e.to_string()
+                                        );
+                                    }
+                                    _ => gloo_console::error!(
+                                        "Unexpected error while communicating with distant ws"
+                                    ),
                                 }
-                                _ => gloo_console::error!(
-                                    "Unexpected error while communicating with distant ws"
-                                ),
-                            },
+                                break;
+                            }
+                        }
+                    }
+
+                    if receiver.is_stopped() {
+                        // this session has been replaced, nothing more to do
+                        if !dead.get() {
+                            return;
                         }
+                        gloo_console::error!("The connection stopped responding");
                     }
                     websocket.set_tx(None);
+
+                    match closed {
+                        Some(CloseReason::Expired) => {
+                            // renew the token, or log in again, then reconnect; a renewal
+                            // that could not reach the server is tried again with the socket
+                            match renew_token(auth).await {
+                                // the user gave up logging in again
+                                Err(ApiError::Expire) => {}
+                                _ => retry(),
+                            }
+                        }
+                        Some(CloseReason::Unauthorized) => {
+                            // the token is refused, renewing it would not help
+                            if relogin(auth).await.is_ok() {
+                                retry();
+                            }
+                        }
+                        Some(CloseReason::Error(s)) => {
+                            gloo_console::log!(s);
+                            logout();
+                        }
+                        // the network dropped, keep the state and try again later
+                        None => retry(),
+                    }
                 });
+
+                move || {
+                    drop((kill_tx, heartbeat));
+                    ws.set_tx(None);
+                }
             },
-            *count,
+            *session,
         );
     }
 
     websocket
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backoff(retries: u32) -> Backoff {
+        Backoff {
+            retries,
+            ..Backoff::default()
+        }
+    }
+
+    #[test]
+    fn delay_doubles_up_to_the_max() {
+        let delays: Vec<_> = (0..7).map(|x| backoff(x).delay_with(1.0)).collect();
+        assert_eq!(delays, [1_000, 2_000, 4_000, 8_000, 16_000, 30_000, 30_000]);
+        // no overflow after many failures
+        assert_eq!(backoff(u32::MAX).delay_with(1.0), BACKOFF_MAX);
+    }
+
+    #[test]
+    fn delay_has_equal_jitter() {
+        assert_eq!(backoff(0).delay_with(0.0), 500);
+        assert_eq!(backoff(0).delay_with(0.5), 750);
+        assert_eq!(backoff(10).delay_with(0.0), BACKOFF_MAX / 2);
+        for random in [0.0, 0.25, 0.99] {
+            let delay = backoff(3).delay_with(random);
+            assert!((4_000..8_000).contains(&delay));
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use chrono::{DateTime, Datelike, Local};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+// ========================// Lang //======================== //
+
+/// A language the UI is translated to, English is the fallback of the others
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Lang {
+    #[default]
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "zh")]
+    Zh,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Zh];
+
+    /// Tag of the language, as in the `lang` attribute of the page
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Zh => "zh-CN",
+        }
+    }
+
+    /// Name of the language in itself, for the switcher
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::Zh => "中文",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|x| code == x.code().to_lowercase() || code.starts_with(&x.prefix()))
+    }
+
+    // primary subtag, "zh-TW" is still closer to Chinese than English
+    fn prefix(&self) -> String {
+        match self.code().split_once('-') {
+            Some((prefix, _)) => prefix.to_owned(),
+            None => self.code().to_owned(),
+        }
+    }
+
+    /// The first language of the browser that is shipped
+    pub fn detect() -> Self {
+        web_sys::window()
+            .map(|x| x.navigator().languages())
+            .into_iter()
+            .flat_map(|x| x.to_vec())
+            .filter_map(|x| x.as_string())
+            .find_map(|x| Self::from_code(&x))
+            .unwrap_or_default()
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Self::En => include_str!("../locales/en.ftl"),
+            Self::Zh => include_str!("../locales/zh.ftl"),
+        }
+    }
+
+    /// Translation of the key, in English if missing, or the key itself
+    pub fn t(&self, key: &str) -> String {
+        self.tr(key, &[])
+    }
+
+    /// Translation of the key with its `{ $name }` placeables filled
+    pub fn tr(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let pattern = lookup(*self, key)
+            .or_else(|| lookup(Lang::En, key))
+            .unwrap_or(key);
+        format_pattern(pattern, args)
+    }
+}
+
+// ========================// Bundle //======================== //
+
+type Bundle = HashMap<&'static str, &'static str>;
+
+thread_local! {
+    // language of the text written outside of the components
+    static CURRENT: Cell<Lang> = const { Cell::new(Lang::En) };
+    // parsed on the first lookup of a language
+    static BUNDLES: RefCell<HashMap<Lang, Bundle>> = RefCell::new(HashMap::new());
+}
+
+fn lookup(lang: Lang, key: &str) -> Option<&'static str> {
+    BUNDLES.with(|bundles| {
+        let mut bundles = bundles.borrow_mut();
+        let bundle = bundles.entry(lang).or_insert_with(|| parse(lang.source()));
+        bundle.get(key).copied()
+    })
+}
+
+/// Messages of a Fluent file, only single line `key = value` messages and
+/// comments are supported
+fn parse(source: &'static str) -> Bundle {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|x| !x.is_empty() && !x.starts_with('#'))
+        .filter_map(|x| x.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+fn format_pattern(pattern: &str, args: &[(&str, &str)]) -> String {
+    let mut text = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find('{') {
+        // a string literal may hold a brace itself
+        let inner = rest[start + 1..].trim_start();
+        let from = match inner.strip_prefix('"').and_then(|x| x.find('"')) {
+            Some(quote) => rest.len() - inner.len() + quote + 2,
+            None => start,
+        };
+        // an unclosed brace is kept as written
+        let end = match rest[from..].find('}') {
+            Some(end) => from + end,
+            None => break,
+        };
+        text.push_str(&rest[..start]);
+        let name = rest[start + 1..end].trim();
+        match name.strip_prefix('$') {
+            Some(name) => match args.iter().find(|(x, _)| *x == name) {
+                Some((_, value)) => text.push_str(value),
+                None => text.push_str(&rest[start..=end]),
+            },
+            // a string literal such as { "{" }
+            None => text.push_str(name.trim_matches('"')),
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+// ========================// Current //======================== //
+
+/// Language of the text written outside of the components
+pub fn lang() -> Lang {
+    CURRENT.with(|x| x.get())
+}
+
+pub fn set_lang(lang: Lang) {
+    CURRENT.with(|x| x.set(lang));
+}
+
+/// Translation of the key in the current language
+pub fn t(key: &str) -> String {
+    lang().t(key)
+}
+
+/// Translation of the key in the current language, with arguments
+pub fn tr(key: &str, args: &[(&str, &str)]) -> String {
+    lang().tr(key, args)
+}
+
+// ========================// Dates //======================== //
+
+fn date_args(time: &DateTime<Local>) -> [(&'static str, String); 4] {
+    let lang = lang();
+    let weekday = time.weekday().number_from_monday();
+    [
+        ("year", time.year().to_string()),
+        ("month", lang.t(&format!("month-{}", time.month()))),
+        ("day", time.day().to_string()),
+        ("weekday", lang.t(&format!("weekday-{}", weekday))),
+    ]
+}
+
+/// A day of this year, with the day of the week
+pub fn format_day(time: &DateTime<Local>) -> String {
+    format_date_key("date-day", time)
+}
+
+/// A day of another year
+pub fn format_day_year(time: &DateTime<Local>) -> String {
+    format_date_key("date-day-year", time)
+}
+
+/// A date in numbers
+pub fn format_date(time: &DateTime<Local>) -> String {
+    let args = [
+        ("year", time.format("%Y").to_string()),
+        ("month", time.format("%m").to_string()),
+        ("day", time.format("%d").to_string()),
+    ];
+    let args: Vec<_> = args.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    tr("date-numeric", &args)
+}
+
+fn format_date_key(key: &str, time: &DateTime<Local>) -> String {
+    let args = date_args(time);
+    let args: Vec<_> = args.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    tr(key, &args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    // the placeables of a message, sorted
+    fn placeables(pattern: &str) -> BTreeSet<&str> {
+        pattern
+            .split('{')
+            .skip(1)
+            .filter_map(|x| x.split_once('}'))
+            .map(|(x, _)| x.trim())
+            .filter(|x| x.starts_with('$'))
+            .collect()
+    }
+
+    #[test]
+    fn parse_messages() {
+        let bundle = parse(
+            "# comment\n\n## Section\nhello = Hello, { $name }!\n  spaced   =   text  \nbroken\nequals = a = b\n",
+        );
+        assert_eq!(bundle.len(), 3);
+        assert_eq!(bundle["hello"], "Hello, { $name }!");
+        assert_eq!(bundle["spaced"], "text");
+        // only the first = splits the key from the value
+        assert_eq!(bundle["equals"], "a = b");
+    }
+
+    #[test]
+    fn format_placeables() {
+        assert_eq!(
+            format_pattern("Hi { $name }!", &[("name", "Ann")]),
+            "Hi Ann!"
+        );
+        assert_eq!(
+            format_pattern("{ $a }{$b} { $a }", &[("a", "1"), ("b", "2")]),
+            "12 1"
+        );
+        // a missing argument stays as written
+        assert_eq!(format_pattern("Hi { $name }", &[]), "Hi { $name }");
+        assert_eq!(format_pattern("{ \"{\" }x{ \"}\" }", &[]), "{x}");
+        assert_eq!(format_pattern("open { brace", &[]), "open { brace");
+    }
+
+    #[test]
+    fn fall_back_to_english() {
+        assert_eq!(Lang::Zh.t("no-such-key"), "no-such-key");
+        assert_eq!(
+            Lang::En.tr("field-invalid", &[("subject", "X")]),
+            "X is not valid"
+        );
+        assert_eq!(Lang::from_code("zh-TW"), Some(Lang::Zh));
+        assert_eq!(Lang::from_code("EN-us"), Some(Lang::En));
+        assert_eq!(Lang::from_code("fr"), None);
+    }
+
+    #[test]
+    fn locales_have_the_same_keys() {
+        let en = parse(Lang::En.source());
+        for lang in Lang::ALL {
+            let bundle = parse(lang.source());
+            let missing: Vec<_> = en.keys().filter(|x| !bundle.contains_key(*x)).collect();
+            let extra: Vec<_> = bundle.keys().filter(|x| !en.contains_key(*x)).collect();
+            assert!(missing.is_empty(), "{:?} misses {:?}", lang, missing);
+            assert!(extra.is_empty(), "{:?} has unknown {:?}", lang, extra);
+
+            for (key, value) in bundle.iter() {
+                assert!(!value.is_empty(), "{:?} leaves {} empty", lang, key);
+                assert_eq!(
+                    placeables(value),
+                    placeables(en[key]),
+                    "{:?} has other arguments in {}",
+                    lang,
+                    key
+                );
+            }
+        }
+    }
+}
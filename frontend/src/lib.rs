@@ -1,7 +1,18 @@
 mod api;
 pub mod components;
+pub mod config;
+mod db;
 mod hooks;
+mod i18n;
+mod notify;
 pub mod pages;
+mod sound;
 pub mod states;
+mod validation;
 
 const PERSIST: &str = "persist";
+
+/// Local storage key of the drafts of a user
+fn drafts_key(user_id: i64) -> String {
+    format!("drafts-{}", user_id)
+}
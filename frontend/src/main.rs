@@ -1,7 +1,10 @@
-use frontend::components::toast::Toast;
+use frontend::components::{theme_provider::ThemeProvider, toast::Toast};
+use frontend::config::RuntimeConfig;
 use frontend::pages::route::{switch, Route};
 use frontend::states::{
     auth::{AuthContext, AuthState},
+    lang::{LangContext, LangState},
+    settings::{SettingsContext, SettingsState},
     toast::{ToastContext, ToastState},
 };
 use yew::{function_component, html, use_reducer, ContextProvider, Html};
@@ -13,13 +16,21 @@ use yew_router::{BrowserRouter, Switch};
 fn App() -> Html {
     let auth = use_reducer(AuthState::default);
     let toast = use_reducer(ToastState::default);
+    let settings = use_reducer(SettingsState::default);
+    let lang = use_reducer(LangState::load);
 
     html! {
         <BrowserRouter>
             <ContextProvider<AuthContext> context={auth}>
                 <ContextProvider<ToastContext> context={toast}>
-                    <Switch<Route> render={switch}/>
-                    <Toast />
+                    <ContextProvider<SettingsContext> context={settings}>
+                        <ContextProvider<LangContext> context={lang}>
+                            <ThemeProvider>
+                                <Switch<Route> render={switch}/>
+                                <Toast />
+                            </ThemeProvider>
+                        </ContextProvider<LangContext>>
+                    </ContextProvider<SettingsContext>>
                 </ContextProvider<ToastContext>>
             </ContextProvider<AuthContext>>
         </BrowserRouter>
@@ -27,5 +38,8 @@ fn App() -> Html {
 }
 
 fn main() {
-    yew::Renderer::<App>::new().render();
+    wasm_bindgen_futures::spawn_local(async {
+        RuntimeConfig::load().await.install();
+        yew::Renderer::<App>::new().render();
+    });
 }
@@ -0,0 +1,225 @@
+use gloo_events::EventListener;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    CanvasRenderingContext2d, Document, HtmlCanvasElement, HtmlImageElement, HtmlLinkElement,
+    Notification, NotificationOptions, NotificationPermission,
+};
+
+// size of the favicon drawn with the badge (px)
+const ICON_SIZE: u32 = 32;
+// the longest preview of a message in a notification (chars)
+const PREVIEW_LEN: usize = 120;
+
+// ========================// Notification //======================== //
+
+/// A desktop notification of a new message
+pub struct Notice {
+    pub title: String,
+    pub body: String,
+    pub icon: String,
+    // notifications of the same room replace each other
+    pub tag: String,
+}
+
+pub fn is_supported() -> bool {
+    js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("Notification")).unwrap_or(false)
+}
+
+pub fn is_granted() -> bool {
+    is_supported() && Notification::permission() == NotificationPermission::Granted
+}
+
+/// Ask the browser for the permission to notify, true once granted
+pub async fn request_permission() -> bool {
+    if !is_supported() {
+        return false;
+    }
+    if let Ok(promise) = Notification::request_permission() {
+        let _ = JsFuture::from(promise).await;
+    }
+    is_granted()
+}
+
+/// The page has the focus of the user
+pub fn has_focus() -> bool {
+    document().and_then(|x| x.has_focus().ok()).unwrap_or(true)
+}
+
+/// Call back whenever the user comes back to the page, as it gets the focus
+/// or is shown again, the listeners are removed when dropped
+pub fn on_return(callback: impl Fn() + 'static) -> Vec<EventListener> {
+    let (window, document) = match (web_sys::window(), document()) {
+        (Some(window), Some(document)) => (window, document),
+        _ => return Vec::new(),
+    };
+    let callback = Rc::new(callback);
+
+    let onfocus = {
+        let callback = callback.clone();
+        EventListener::new(&window, "focus", move |_| callback())
+    };
+    let onvisible = {
+        let target = document.clone();
+        EventListener::new(&target, "visibilitychange", move |_| {
+            if !document.hidden() {
+                callback();
+            }
+        })
+    };
+    vec![onfocus, onvisible]
+}
+
+/// Show the notice if allowed, the page gets the focus back when it is clicked
+pub fn show(notice: Notice, onclick: impl FnOnce() + 'static) {
+    if !is_granted() {
+        return;
+    }
+
+    let mut body: String = notice.body.chars().take(PREVIEW_LEN).collect();
+    if body.len() < notice.body.len() {
+        body.push('…');
+    }
+
+    let mut options = NotificationOptions::new();
+    options.body(&body).icon(&notice.icon).tag(&notice.tag);
+
+    if let Ok(notification) = Notification::new_with_options(&notice.title, &options) {
+        let onclick = {
+            let notification = notification.clone();
+            Closure::once_into_js(move || {
+                if let Some(window) = web_sys::window() {
+                    let _ = window.focus();
+                }
+                notification.close();
+                onclick();
+            })
+        };
+        notification.set_onclick(Some(onclick.unchecked_ref()));
+    }
+}
+
+// ========================// Badge //======================== //
+
+// title and favicon of the page before any badge was drawn
+struct Original {
+    title: String,
+    icon: String,
+    image: Option<HtmlImageElement>,
+    // count to draw once the image is loaded
+    count: usize,
+    _onload: Option<EventListener>,
+}
+
+thread_local! {
+    static ORIGINAL: RefCell<Option<Original>> = const { RefCell::new(None) };
+}
+
+fn document() -> Option<Document> {
+    web_sys::window().and_then(|x| x.document())
+}
+
+fn favicon(document: &Document) -> Option<HtmlLinkElement> {
+    document
+        .query_selector("link[rel~='icon']")
+        .ok()
+        .flatten()
+        .and_then(|x| x.dyn_into::<HtmlLinkElement>().ok())
+}
+
+/// Put the number of unread messages in the title and on the favicon
+pub fn set_badge(count: usize) {
+    let document = match document() {
+        Some(document) => document,
+        None => return,
+    };
+    let link = favicon(&document);
+
+    ORIGINAL.with(|original| {
+        let mut original = original.borrow_mut();
+        let original = original.get_or_insert_with(|| capture(&document, link.as_ref()));
+        original.count = count;
+
+        if count == 0 {
+            document.set_title(&original.title);
+        } else {
+            document.set_title(&format!("({}) {}", count, original.title));
+        }
+
+        if let Some(link) = link {
+            if count == 0 {
+                link.set_href(&original.icon);
+            } else if let Some(url) = draw_badge(original.image.as_ref(), count) {
+                link.set_href(&url);
+            }
+        }
+    });
+}
+
+fn capture(document: &Document, link: Option<&HtmlLinkElement>) -> Original {
+    let icon = link.map(|x| x.href()).unwrap_or_default();
+    let image = HtmlImageElement::new().ok().filter(|_| !icon.is_empty());
+
+    // the favicon may still be loading when the first badge is drawn
+    let onload = image.as_ref().map(|image| {
+        image.set_src(&icon);
+        EventListener::once(image, "load", |_| {
+            let count = ORIGINAL.with(|x| x.borrow().as_ref().map(|x| x.count));
+            if let Some(count) = count.filter(|x| *x > 0) {
+                set_badge(count);
+            }
+        })
+    });
+
+    Original {
+        title: document.title(),
+        icon,
+        image,
+        count: 0,
+        _onload: onload,
+    }
+}
+
+/// Data URL of the favicon with the count in a red circle
+fn draw_badge(image: Option<&HtmlImageElement>, count: usize) -> Option<String> {
+    let canvas = document()?
+        .create_element("canvas")
+        .ok()?
+        .dyn_into::<HtmlCanvasElement>()
+        .ok()?;
+    canvas.set_width(ICON_SIZE);
+    canvas.set_height(ICON_SIZE);
+
+    let ctx = canvas
+        .get_context("2d")
+        .ok()??
+        .dyn_into::<CanvasRenderingContext2d>()
+        .ok()?;
+
+    let size = ICON_SIZE as f64;
+    if let Some(image) = image.filter(|x| x.complete() && x.natural_width() > 0) {
+        let _ = ctx.draw_image_with_html_image_element_and_dw_and_dh(image, 0.0, 0.0, size, size);
+    }
+
+    let radius = size * 0.34;
+    let (x, y) = (size - radius, radius);
+    ctx.begin_path();
+    let _ = ctx.arc(x, y, radius, 0.0, std::f64::consts::PI * 2.0);
+    ctx.set_fill_style(&JsValue::from_str("#f43f5e"));
+    ctx.fill();
+
+    let label = if count > 99 {
+        "99+".to_owned()
+    } else {
+        count.to_string()
+    };
+    let font = if label.len() > 1 { 13.0 } else { 17.0 };
+    ctx.set_font(&format!("bold {}px sans-serif", font));
+    ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
+    ctx.set_fill_style(&JsValue::from_str("#ffffff"));
+    let _ = ctx.fill_text(&label, x, y + 1.0);
+
+    canvas.to_data_url().ok()
+}
@@ -0,0 +1,50 @@
+use super::route::Route;
+use crate::{
+    components::{
+        account_section::AccountSection, invitation_panel::InvitationPanel, user_table::UserTable,
+    },
+    hooks::use_lang,
+};
+use yew::{function_component, html, Html};
+use yew_router::prelude::Link;
+
+// ========================// Admin //======================== //
+
+#[function_component]
+pub fn Admin() -> Html {
+    let lang = use_lang();
+    html! {
+        <div class="h-screen w-full min-w-fit p-5 lg:py-20 flex items-center justify-center bg-cover"
+            style="background-image: url('/assets/pic/chat-bg.jpg')">
+            <div class="h-full max-w-5xl w-full rounded-md shadow-lg overflow-hidden flex items-center justify-center">
+                // nav bar
+                <div class="shrink-0 h-full w-16 pb-3 flex flex-col bg-nav">
+                    <div class="grow">
+                        <div class="h-16 flex items-center justify-center">
+                            <Link<Route> to={Route::Chat} classes="p-2 rounded-full cursor-pointer text-slate-500 hover:text-slate-300">
+                                <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
+                                    stroke="currentColor" class="w-7 h-7">
+                                    <path stroke-linecap="round" stroke-linejoin="round" d="M10.5 19.5L3 12m0 0l7.5-7.5M3 12h18" />
+                                </svg>
+                            </Link<Route>>
+                        </div>
+                    </div>
+
+                    <div class="shrink-0 relative h-16 flex justify-center items-center">
+                        <AccountSection />
+                    </div>
+                </div>
+
+                <div class="grow h-full bg-slate-100 flex flex-col">
+                    <div class="shrink-0 h-14 border-b px-4 flex items-center justify-center">
+                        <p class="text-slate-700 font-semibold">{lang.t("admin-console")}</p>
+                    </div>
+                    <div class="grow p-5 space-y-6 overflow-y-scroll hover:scrollbar no-scrollbar">
+                        <UserTable />
+                        <InvitationPanel />
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,45 +1,133 @@
-use super::ChatPage;
+use super::{route::Route, ChatPage, Pane};
 use crate::{
-    components::{account_section::AccountSection, friend_page::FriendPage, room_page::RoomPage},
-    hooks::use_websocket,
-    states::{chat::ChatState, friend::FriendPageTrigger, room::RoomPageTrigger},
+    components::{
+        account_section::AccountSection, friend_page::FriendPage, quick_switcher::QuickSwitcher,
+        room_page::RoomPage,
+    },
+    hooks::{use_lang, use_pane, use_subscribe, use_websocket},
+    notify,
+    states::{
+        chat::ChatState, settings::SettingsContext, store::Topic, theme::ThemeContext,
+        websocket::ConnStatus,
+    },
 };
 use yew::{
-    classes, function_component, html, use_state_eq, Callback, Html, Properties, UseStateHandle,
+    classes, function_component, html, use_context, use_effect_with_deps, AttrValue, Callback,
+    Html, Properties,
 };
+use yew_router::prelude::use_route;
 
 // ========================// Chat //======================== //
 
 #[function_component]
 pub fn Chat() -> Html {
+    let lang = use_lang();
     let ws = use_websocket();
-    let curr_page = use_state_eq(|| ChatPage::Room);
+    let settings = use_context::<SettingsContext>().unwrap();
+    let theme = use_context::<ThemeContext>().unwrap();
+    let pane = use_pane();
+    // the page, room and friend shown are those of the route
+    let route = use_route::<Route>().unwrap_or(Route::Chat);
+    let curr_page = match route {
+        Route::NewFriend | Route::Friend { .. } => ChatPage::Friend,
+        _ => ChatPage::Room,
+    };
+
+    // the unread messages of the rooms not muted, in the title and on the favicon
+    let version = use_subscribe(ws.store.clone(), vec![Topic::Rooms]);
+    {
+        let chat = ChatState::from_ws(&ws);
+        use_effect_with_deps(
+            move |(_, muted)| {
+                notify::set_badge(chat.count_unreads(muted));
+                || notify::set_badge(0)
+            },
+            (version, settings.muted.clone()),
+        );
+    }
+
+    // the messages that came in while the user was away are read once back
+    {
+        let chat = ChatState::from_ws(&ws);
+        use_effect_with_deps(
+            move |_| {
+                let listeners = notify::on_return(move || chat.read_curr_room());
+                move || drop(listeners)
+            },
+            (),
+        );
+    }
+
+    // a room opened from a notification is shown on the room page
+    let focus = use_subscribe(ws.store.clone(), vec![Topic::Focus]);
+    {
+        let chat = ChatState::from_ws(&ws);
+        let pane = pane.clone();
+        use_effect_with_deps(
+            move |focus| {
+                if *focus > 0 {
+                    pane.goto(Route::room(chat.curr_room()), Pane::Conversation);
+                }
+                || ()
+            },
+            focus,
+        );
+    }
 
+    // a page opens on the room or friend shown last, in the same pane
     let nav_page = {
-        let curr_page = curr_page.clone();
+        let chat = ChatState::from_ws(&ws);
+        let pane = pane.clone();
         Callback::from(move |page: ChatPage| {
-            curr_page.set(page);
+            let route = match page {
+                ChatPage::Room => Route::room(chat.curr_room()),
+                ChatPage::Friend => Route::friend(chat.curr_friend()),
+            };
+            pane.goto(route, pane.pane());
         })
     };
 
-    let show_page = match *curr_page {
-        ChatPage::Room => html! {
-            <RoomPage chat={ChatState::from_ws(&ws)} trigger={RoomPageTrigger::from_ws(&ws)} />
+    let chat = ChatState::from_ws(&ws);
+    let show_page = match route {
+        Route::Friend { id } => html! {
+            <FriendPage chat={chat.clone()} friend_id={id} nav_page={nav_page.clone()} />
+        },
+        Route::NewFriend => html! {
+            <FriendPage chat={chat.clone()} friend_id={-1} nav_page={nav_page.clone()} />
+        },
+        Route::Room { id } => html! {
+            <RoomPage chat={chat.clone()} room_id={id} />
+        },
+        Route::NewRoom => html! {
+            <RoomPage chat={chat.clone()} room_id={-1} />
+        },
+        // the room shown last
+        _ => html! {
+            <RoomPage chat={chat.clone()} room_id={chat.curr_room()} />
+        },
+    };
+
+    let show_status = match ws.status() {
+        ConnStatus::Reconnecting => html! {
+            <ConnBanner text={lang.t("conn-reconnecting")} color="bg-amber-500" />
         },
-        ChatPage::Friend => html! {
-            <FriendPage chat={ChatState::from_ws(&ws)} trigger={FriendPageTrigger::from_ws(&ws)}
-                nav_page={nav_page.clone()}  />
+        ConnStatus::Offline => html! {
+            <ConnBanner text={lang.t("conn-offline")} color="bg-slate-500" />
         },
+        _ => html! {},
     };
 
     html! {
-        <div class="h-screen w-full min-w-fit p-5 lg:py-20 flex items-center justify-center bg-cover"
-            style="background-image: url('/assets/pic/chat-bg.jpg')">
-            <div class="h-full max-w-5xl w-full rounded-md shadow-lg overflow-hidden flex items-center justify-center">
+        <div class="h-[100dvh] w-full md:min-w-fit md:p-5 lg:py-20 flex items-center justify-center bg-cover bg-slate-300"
+            style={theme.background_style()}>
+            // the nav bar is at the bottom of a narrow viewport, and only beside the lists
+            <div class="relative h-full max-w-5xl w-full md:rounded-md shadow-lg overflow-hidden flex flex-col-reverse md:flex-row md:items-center md:justify-center">
+                {show_status}
+
                 // nav bar
-                <div class="shrink-0 h-full w-16 pb-3 flex flex-col bg-slate-800">
-                    <div class="grow">
-                        <div class="h-16 flex items-center justify-center">
+                <div class={classes!("shrink-0", "h-14", "w-full", "md:h-full", "md:w-16", "md:pb-3", "md:flex-col", "bg-nav", pane.display(Pane::List))}>
+                    <div class="grow flex md:block">
+                        <div class="hidden h-16 md:flex items-center justify-center">
                             <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="2"
                                 stroke="currentColor" class="w-8 h-8 stroke-indigo-400">
                                 <path stroke-linecap="round" stroke-linejoin="round"
@@ -47,29 +135,53 @@ pub fn Chat() -> Html {
                             </svg>
                         </div>
 
-                        <div class="flex flex-col items-center space-y-1">
+                        <div class="grow flex items-center justify-around md:flex-col md:justify-start md:space-y-1">
                             <RoomEntry nav_page={nav_page.clone()} curr_page={curr_page.clone()} />
                             <FriendEntry nav_page={nav_page.clone()} curr_page={curr_page.clone()} />
                         </div>
                     </div>
 
-                    <div class="shrink-0 relative h-16 flex justify-center items-center">
+                    <div class="shrink-0 relative w-16 h-14 md:w-auto md:h-16 flex justify-center items-center">
                         <AccountSection />
                     </div>
                 </div>
 
-                {show_page}
+                // the lists and the conversation, side by side on a wide viewport
+                <div class="grow min-h-0 w-full md:w-auto md:h-full flex">
+                    {show_page}
+                </div>
+
+                <QuickSwitcher {chat} />
             </div>
         </div>
     }
 }
 
+// ========================// ConnBanner //======================== //
+
+#[derive(PartialEq, Properties)]
+struct ConnBannerProps {
+    text: AttrValue,
+    color: AttrValue,
+}
+
+#[function_component]
+fn ConnBanner(props: &ConnBannerProps) -> Html {
+    html! {
+        <div class="absolute z-10 top-3 left-1/2 -translate-x-1/2 pointer-events-none">
+            <p class={classes!("px-4", "py-1", "rounded-full", "shadow-md", "text-xs", "font-semibold", "text-white", props.color.to_string())}>
+                {props.text.clone()}
+            </p>
+        </div>
+    }
+}
+
 // ========================// RoomEntry //======================== //
 
 #[derive(PartialEq, Properties)]
 struct RoomEntryProps {
     nav_page: Callback<ChatPage>,
-    curr_page: UseStateHandle<ChatPage>,
+    curr_page: ChatPage,
 }
 
 #[function_component]
@@ -79,10 +191,10 @@ fn RoomEntry(props: &RoomEntryProps) -> Html {
         move |_| nav_page.emit(ChatPage::Room)
     };
 
-    let cls = if *props.curr_page == ChatPage::Room {
-        "p-2 rounded-full cursor-pointer text-slate-300".to_owned()
+    let cls = if props.curr_page == ChatPage::Room {
+        "p-3 md:p-2 rounded-full cursor-pointer text-slate-300".to_owned()
     } else {
-        "p-2 rounded-full cursor-pointer text-slate-500 hover:text-slate-300".to_owned()
+        "p-3 md:p-2 rounded-full cursor-pointer text-slate-500 hover:text-slate-300".to_owned()
     };
 
     html! {
@@ -101,7 +213,7 @@ fn RoomEntry(props: &RoomEntryProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct FriendEntryProps {
     nav_page: Callback<ChatPage>,
-    curr_page: UseStateHandle<ChatPage>,
+    curr_page: ChatPage,
 }
 
 #[function_component]
@@ -111,10 +223,10 @@ fn FriendEntry(props: &FriendEntryProps) -> Html {
         move |_| nav_page.emit(ChatPage::Friend)
     };
 
-    let cls = if *props.curr_page == ChatPage::Friend {
-        "p-2 rounded-full cursor-pointer text-slate-300".to_owned()
+    let cls = if props.curr_page == ChatPage::Friend {
+        "p-3 md:p-2 rounded-full cursor-pointer text-slate-300".to_owned()
     } else {
-        "p-2 rounded-full cursor-pointer text-slate-500 hover:text-slate-300".to_owned()
+        "p-3 md:p-2 rounded-full cursor-pointer text-slate-500 hover:text-slate-300".to_owned()
     };
 
     html! {
@@ -1,3 +1,4 @@
+mod admin;
 mod chat;
 mod login;
 mod page_not_found;
@@ -9,3 +10,14 @@ pub enum ChatPage {
     Room,
     Friend,
 }
+
+/// What the chat page shows on a narrow viewport, one at a time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pane {
+    // the rooms or the friends
+    List,
+    // a room or a friend
+    Conversation,
+    // the members of a room
+    Details,
+}
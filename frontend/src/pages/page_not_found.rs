@@ -1,9 +1,11 @@
+use crate::hooks::use_lang;
 use yew::{function_component, html, Html};
 
 // ========================// 404 //======================== //
 
 #[function_component]
 pub fn PageNotFound() -> Html {
+    let lang = use_lang();
     html! {
         <div class="relative flex items-top justify-center min-h-screen bg-slate-100 sm:items-center sm:pt-0">
             <div class="max-w-xl mx-auto sm:px-6 lg:px-8">
@@ -12,7 +14,7 @@ pub fn PageNotFound() -> Html {
                         {"404"}
                     </div>
                     <div class="ml-4 text-lg text-slate-500 uppercase tracking-wider">
-                        {"Not Found"}
+                        {lang.t("not-found")}
                     </div>
                 </div>
             </div>
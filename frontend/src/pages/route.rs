@@ -1,7 +1,10 @@
-use super::{chat::Chat, login::Login, page_not_found::PageNotFound, register::Register};
-use crate::components::auth_guard::AuthGuard;
+use super::{
+    admin::Admin, chat::Chat, login::Login, page_not_found::PageNotFound, register::Register,
+};
+use crate::components::auth_guard::{AdminGuard, AuthGuard};
+use serde::{Deserialize, Serialize};
 use yew::{html, Html};
-use yew_router::Routable;
+use yew_router::{history::Location, Routable};
 
 // ========================// Route //======================== //
 
@@ -9,20 +12,112 @@ use yew_router::Routable;
 pub enum Route {
     #[at("/")]
     Chat,
+    #[at("/rooms/new")]
+    NewRoom,
+    #[at("/rooms/:id")]
+    Room { id: i64 },
+    #[at("/friends/new")]
+    NewFriend,
+    #[at("/friends/:id")]
+    Friend { id: i64 },
     #[at("/register")]
     Register,
     #[at("/login")]
     Login,
+    #[at("/admin")]
+    Admin,
     #[not_found]
     #[at("/404")]
     NotFound,
 }
 
+impl Route {
+    /// Route of a room of the chat page, `-1` is the new room form
+    pub fn room(room_id: i64) -> Self {
+        match room_id {
+            -1 => Self::NewRoom,
+            0 => Self::Chat,
+            id => Self::Room { id },
+        }
+    }
+
+    /// Route of a friend of the chat page, the new friend form if none
+    pub fn friend(friend_id: i64) -> Self {
+        match friend_id {
+            id if id > 0 => Self::Friend { id },
+            _ => Self::NewFriend,
+        }
+    }
+
+    /// Route to go to after logging in, kept in the query of the login page
+    pub fn destination(location: Option<Location>) -> Self {
+        location
+            .and_then(|x| x.query::<Destination>().ok())
+            .and_then(|x| Self::recognize(&x.next))
+            .filter(|x| !matches!(x, Self::Login | Self::Register | Self::NotFound))
+            .unwrap_or(Self::Chat)
+    }
+}
+
+/// Query of the login page
+#[derive(Serialize, Deserialize)]
+pub struct Destination {
+    // path of the page the user was sent away from
+    pub next: String,
+}
+
 pub fn switch(route: Route) -> Html {
     match route {
-        Route::Chat => html! { <AuthGuard><Chat /></AuthGuard> },
+        Route::Chat
+        | Route::NewRoom
+        | Route::Room { .. }
+        | Route::NewFriend
+        | Route::Friend { .. } => html! { <AuthGuard><Chat /></AuthGuard> },
         Route::Register => html! { <Register /> },
         Route::Login => html! { <Login /> },
+        Route::Admin => html! { <AdminGuard><Admin /></AdminGuard> },
         Route::NotFound => html! { <PageNotFound /> },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yew_router::history::{History, MemoryHistory};
+
+    fn destination(next: &str) -> Route {
+        let history = MemoryHistory::new();
+        let next = next.to_owned();
+        history
+            .replace_with_query("/login", Destination { next })
+            .unwrap();
+        Route::destination(Some(history.location()))
+    }
+
+    #[test]
+    fn back_to_the_page_left() {
+        assert!(destination("/rooms/3") == Route::Room { id: 3 });
+        assert!(destination("/friends/8") == Route::Friend { id: 8 });
+        assert!(destination("/admin") == Route::Admin);
+    }
+
+    #[test]
+    fn chat_page_by_default() {
+        assert!(Route::destination(None) == Route::Chat);
+        assert!(Route::destination(Some(MemoryHistory::new().location())) == Route::Chat);
+        assert!(destination("") == Route::Chat);
+        // never back to the forms of the session or a missing page
+        assert!(destination("/login") == Route::Chat);
+        assert!(destination("/register") == Route::Chat);
+        assert!(destination("/rooms/abc") == Route::Chat);
+    }
+
+    #[test]
+    fn chat_routes() {
+        assert!(Route::room(-1) == Route::NewRoom);
+        assert!(Route::room(0) == Route::Chat);
+        assert!(Route::room(5) == Route::Room { id: 5 });
+        assert!(Route::friend(0) == Route::NewFriend);
+        assert!(Route::friend(6) == Route::Friend { id: 6 });
+    }
+}
@@ -0,0 +1,144 @@
+use gloo_net::http::Request;
+use std::{cell::RefCell, collections::HashMap};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{AudioBuffer, AudioContext};
+
+// a cue played less than this long after the last one is skipped (ms)
+const COOLDOWN: f64 = 1_500.0;
+
+/// Sound packs bundled in `assets/sounds`, by directory and label key
+pub const PACKS: [(&str, &str); 3] = [
+    ("chime", "sound-chime"),
+    ("pop", "sound-pop"),
+    ("bell", "sound-bell"),
+];
+
+/// Pack name of no sound at all
+pub const OFF: &str = "off";
+/// Label key of no sound at all
+pub const OFF_LABEL: &str = "sound-off";
+
+// ========================// Cue //======================== //
+
+/// What a sound tells the user about
+#[derive(Clone, Copy, PartialEq)]
+pub enum Cue {
+    Message,
+    // a message naming the current user
+    Mention,
+    // an incoming friend request
+    Friend,
+}
+
+impl Cue {
+    fn url(&self, pack: &str) -> String {
+        let name = match self {
+            Self::Message => "message",
+            Self::Mention => "mention",
+            Self::Friend => "friend",
+        };
+        format!("/assets/sounds/{}/{}.wav", pack, name)
+    }
+}
+
+// ========================// Player //======================== //
+
+#[derive(Default)]
+struct Player {
+    // created on the first cue, browsers only allow it after a user gesture
+    ctx: Option<AudioContext>,
+    // decoded sounds by url
+    buffers: HashMap<String, AudioBuffer>,
+    // when the last cue was played (ms)
+    last_played: f64,
+}
+
+thread_local! {
+    static PLAYER: RefCell<Player> = RefCell::new(Player::default());
+}
+
+/// Play the cue from the pack, at a volume between 0 and 100, unless another
+/// cue has just been played
+pub fn play(pack: &str, cue: Cue, volume: u8) {
+    let now = js_sys::Date::now();
+    let ready = PLAYER.with(|player| {
+        let mut player = player.borrow_mut();
+        if now - player.last_played < COOLDOWN {
+            return false;
+        }
+        player.last_played = now;
+        true
+    });
+
+    if ready {
+        preview(pack, cue, volume);
+    }
+}
+
+/// Play the cue right away, for the settings
+pub fn preview(pack: &str, cue: Cue, volume: u8) {
+    if pack == OFF || volume == 0 || PACKS.iter().all(|(x, _)| *x != pack) {
+        return;
+    }
+
+    let url = cue.url(pack);
+    spawn_local(async move {
+        if let Err(e) = play_url(url, volume).await {
+            gloo_console::error!("Failed to play the sound:", e);
+        }
+    });
+}
+
+async fn play_url(url: String, volume: u8) -> Result<(), JsValue> {
+    let ctx = PLAYER.with(|player| -> Result<AudioContext, JsValue> {
+        let mut player = player.borrow_mut();
+        match &player.ctx {
+            Some(ctx) => Ok(ctx.clone()),
+            None => {
+                let ctx = AudioContext::new()?;
+                player.ctx = Some(ctx.clone());
+                Ok(ctx)
+            }
+        }
+    })?;
+    // suspended until the user has interacted with the page
+    let _ = ctx.resume();
+
+    let cached = PLAYER.with(|player| player.borrow().buffers.get(&url).cloned());
+    let buffer = match cached {
+        Some(buffer) => buffer,
+        None => {
+            let buffer = load(&ctx, &url).await?;
+            PLAYER.with(|player| {
+                let mut player = player.borrow_mut();
+                player.buffers.insert(url, buffer.clone());
+            });
+            buffer
+        }
+    };
+
+    let source = ctx.create_buffer_source()?;
+    source.set_buffer(Some(&buffer));
+    let gain = ctx.create_gain()?;
+    gain.gain().set_value(volume.min(100) as f32 / 100.0);
+
+    source.connect_with_audio_node(&gain)?;
+    gain.connect_with_audio_node(&ctx.destination())?;
+    source.start()
+}
+
+async fn load(ctx: &AudioContext, url: &str) -> Result<AudioBuffer, JsValue> {
+    let resp = Request::get(url)
+        .send()
+        .await
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let bytes = resp
+        .binary()
+        .await
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let data = js_sys::Uint8Array::from(bytes.as_slice()).buffer();
+    let decoded = JsFuture::from(ctx.decode_audio_data(&data)?).await?;
+    decoded.dyn_into::<AudioBuffer>()
+}
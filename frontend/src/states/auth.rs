@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use shared::{
     auth::{LoginResponse, RenewTokenResponse},
     user::UserInfo,
@@ -10,13 +11,18 @@ use yew::prelude::*;
 #[derive(Default, PartialEq)]
 pub struct AuthState {
     pub authorized: bool,
+    // the login has expired and is waiting for the user to enter the password again
+    pub expired: bool,
     pub user: UserInfo,
     pub token: String,
+    pub expire_at: DateTime<Utc>,
 }
 
 pub enum AuthAction {
     Set(LoginResponse),
     Renew(RenewTokenResponse),
+    Update(UserInfo),
+    Expire,
     Clear,
 }
 
@@ -27,16 +33,41 @@ impl Reducible for AuthState {
         match action {
             AuthAction::Set(data) => Self {
                 authorized: true,
+                expired: false,
                 user: data.user,
                 token: data.access_token,
+                expire_at: data.expire_at,
             }
             .into(),
             AuthAction::Renew(data) => {
                 let user = self.user.clone();
                 Self {
                     authorized: self.authorized,
+                    expired: false,
                     user,
                     token: data.access_token,
+                    expire_at: data.expire_at,
+                }
+                .into()
+            }
+            AuthAction::Update(user) => Self {
+                authorized: self.authorized,
+                expired: self.expired,
+                user,
+                token: self.token.clone(),
+                expire_at: self.expire_at,
+            }
+            .into(),
+            AuthAction::Expire => {
+                if !self.authorized || self.expired {
+                    return self;
+                }
+                Self {
+                    authorized: true,
+                    expired: true,
+                    user: self.user.clone(),
+                    token: self.token.clone(),
+                    expire_at: self.expire_at,
                 }
                 .into()
             }
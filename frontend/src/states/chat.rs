@@ -1,31 +1,60 @@
 use super::{
+    command::{Command, CommandSpec, Completion, COMMANDS, MAX_SUGGESTIONS},
+    settings::SettingsState,
+    store::{Store, Topic},
+    switcher::{match_score, rank, MAX_RESULTS},
     types::{
-        time_ago, ConvertToMessage, CurrRoomItem, FriendAttr, FriendItem, MemberItem,
-        MessageContent, MessageItem, PersonalRoom, PublicRoom, Relation, RoomItem,
+        latest_message, parse_room_meta, ConvertToMessage, CurrRoomItem, FriendAttr, FriendItem,
+        MemberItem, MessageItem, PendingAttr, PendingContent, PersonalRoom, PublicRoom, Relation,
+        RoomAttr, RoomItem, SendStatus, Suggestion, SwitchItem,
     },
     websocket::WebSocketState,
 };
-use crate::hooks::UseWebSocketHandle;
-use chrono::Local;
+use crate::{
+    db::{
+        cache::{self, CachedState},
+        outbox::{self, OutboxItem},
+    },
+    hooks::UseWebSocketHandle,
+    i18n,
+    notify::{self, Notice},
+    sound::{self, Cue},
+    validation::validation_error,
+};
+use chrono::{DateTime, Local};
 use futures::SinkExt;
 use gloo_net::websocket::Message;
+use gloo_timers::callback::Timeout;
 use shared::{
-    event::ClientEvent,
+    event::{ClientEvent, ServerEvent, ServerMessage},
     friend::{AcceptFriendRequest, AddFriendRequest, DeleteFriendRequest, RefuseFriendRequest},
     member::{AddMembersRequest, DeleteMembersRequest},
     message::NewMessageRequest,
     room::{DeleteRoomRequest, LeaveRoomRequest, NewRoomNameResquest, NewRoomRequest},
+    session::CloseReason,
     user::GetUserByNameResponse,
 };
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
-use wasm_bindgen::UnwrapThrowExt;
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    ops::Range,
+    rc::Rc,
+};
+use uuid::Uuid;
+use validator::Validate;
 use wasm_bindgen_futures::spawn_local;
 use yew::AttrValue;
 
+// a sent message is marked as failed if the server does not echo it in time (ms)
+const SEND_TIMEOUT: u32 = 10_000;
+// the cache is written once no event has come for this long (ms)
+const CACHE_DELAY: u32 = 2_000;
+
 // ========================// ChatState //======================== //
 
 pub struct ChatState {
     inner: Rc<RefCell<WebSocketState>>,
+    store: Rc<Store>,
 }
 
 impl PartialEq for ChatState {
@@ -38,6 +67,7 @@ impl Clone for ChatState {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            store: self.store.clone(),
         }
     }
 }
@@ -46,23 +76,121 @@ impl ChatState {
     pub fn from_ws(ws: &UseWebSocketHandle) -> Self {
         Self {
             inner: ws.inner.clone(),
+            store: ws.store.clone(),
         }
     }
 
+    pub fn store(&self) -> Rc<Store> {
+        self.store.clone()
+    }
+
+    /// Apply a message of the server, then tell the user about it and keep
+    /// the state for the next page
+    pub fn handle_message(
+        &self,
+        msg: ServerMessage,
+        settings: &SettingsState,
+    ) -> Result<(), CloseReason> {
+        let (received, friend_request) = match &msg.event {
+            ServerEvent::ReceiveMessage(resp) => (Some(resp.room_id), false),
+            ServerEvent::AddFriend(resp) => {
+                let relation = Relation::from_info(&resp.friend);
+                (None, matches!(relation, Relation::IncomingAdding))
+            }
+            _ => (None, false),
+        };
+        let cached = changes_cache(&msg.event);
+        self.inner.borrow_mut().handle_message(msg, &self.store)?;
+
+        if let Some(room_id) = received {
+            self.announce_message(room_id, settings);
+        }
+        if friend_request {
+            sound::play(&settings.sound_pack, Cue::Friend, settings.volume);
+        }
+        if cached {
+            self.save_cache();
+        }
+        Ok(())
+    }
+
+    /// Tell about the message just received in a room, with a sound and on the desktop
+    fn announce_message(&self, room_id: i64, settings: &SettingsState) {
+        if settings.is_muted(room_id) {
+            return;
+        }
+        let focused = notify::has_focus();
+
+        if let Some(cue) = self.message_cue(room_id, focused) {
+            sound::play(settings.room_sound(room_id), cue, settings.volume);
+        }
+        if !settings.notifications {
+            return;
+        }
+        if let Some(notice) = self.notice(room_id, &settings.muted, focused) {
+            let chat = self.clone();
+            notify::show(notice, move || chat.focus_room(room_id));
+        }
+    }
+
+    /// Write the state to the cache once the events stop for a while, the
+    /// cached state is only replaced by one synced with the server
+    fn save_cache(&self) {
+        let mut inner = self.inner.borrow_mut();
+        if !inner.synced {
+            return;
+        }
+        // the timer is kept by the state, it must not keep the state alive
+        let state = Rc::downgrade(&self.inner);
+        let timer = Timeout::new(CACHE_DELAY, move || {
+            if let Some(inner) = state.upgrade() {
+                let inner = inner.borrow();
+                cache::save(inner.curr_user, &inner);
+            }
+        });
+        // the write planned before is cancelled
+        inner.cache_timer = Some(timer);
+    }
+
     pub fn curr_room(&self) -> i64 {
         self.inner.borrow().curr_room
     }
 
     pub fn set_curr_room(&self, room_id: i64) {
-        self.inner.borrow_mut().curr_room = room_id;
-        if let Some(room) = self
-            .inner
-            .borrow_mut()
-            .rooms
-            .iter_mut()
-            .find(|r| r.id == room_id)
-        {
-            room.unreads = 0;
+        let mut inner = self.inner.borrow_mut();
+        // the room left shows its draft again
+        let left_draft = inner.curr_room != room_id && inner.drafts.contains_key(&inner.curr_room);
+        let had_unreads = inner.open_room(room_id);
+        drop(inner);
+
+        if had_unreads || left_draft {
+            self.store.publish(&[Topic::Rooms]);
+        }
+    }
+
+    /// Mark the messages received in the current room while the user was away
+    /// as read, they stay after the divider until another room is opened
+    pub fn read_curr_room(&self) {
+        let room_id = self.curr_room();
+        if self.inner.borrow_mut().open_room(room_id) {
+            self.store
+                .publish(&[Topic::Rooms, Topic::Messages(room_id)]);
+        }
+    }
+
+    /// Show a room from outside the room list, the chat page switches to it
+    pub fn focus_room(&self, room_id: i64) {
+        self.set_curr_room(room_id);
+        self.store.publish(&[Topic::Focus]);
+    }
+
+    /// Show a room picked in the quick switcher, the private room of a friend
+    /// that is not loaded yet is fetched with the other rooms
+    pub fn switch_room(&self, room_id: i64) {
+        let loaded = self.inner.borrow().rooms.contains(room_id);
+        self.focus_room(room_id);
+        if !loaded {
+            self.get_user_rooms();
         }
     }
 
@@ -75,24 +203,18 @@ impl ChatState {
     }
 
     pub fn get_curr_room(&self, room_id: i64) -> Option<CurrRoomItem> {
-        if let Some(room) = self.inner.borrow().rooms.iter().find(|r| r.id == room_id) {
+        if let Some(room) = self.inner.borrow().rooms.get(room_id) {
             let curr_user = self.inner.borrow().curr_user;
 
             match room.category.as_str() {
                 "public" => Some(CurrRoomItem::Public(PublicRoom::from_attr(room, curr_user))),
                 "private" => {
                     if let Some(member) = room.members.iter().find(|x| x.id != curr_user) {
-                        if let Some(friend) = self
-                            .inner
+                        self.inner
                             .borrow()
                             .friends
-                            .iter()
-                            .find(|x| x.id == member.id)
-                        {
-                            Some(CurrRoomItem::Private(friend.clone()))
-                        } else {
-                            None
-                        }
+                            .get(member.id)
+                            .map(|friend| CurrRoomItem::Private(friend.clone()))
                     } else {
                         None
                     }
@@ -106,34 +228,43 @@ impl ChatState {
     }
 
     pub fn get_rank(&self, room_id: i64) -> AttrValue {
-        if let Some(room) = self.inner.borrow().rooms.iter().find(|x| x.id == room_id) {
+        if let Some(room) = self.inner.borrow().rooms.get(room_id) {
             let curr_user = self.inner.borrow().curr_user;
             room.members
                 .iter()
                 .find(|x| x.id == curr_user)
-                .map(|x| x.rank.clone().into())
+                .map(|x| x.rank.clone())
                 .unwrap_or(AttrValue::from("member"))
         } else {
             AttrValue::from("member")
         }
     }
 
+    /// Current time corrected by the clock skew to the server
+    pub fn now(&self) -> DateTime<Local> {
+        self.inner.borrow().heartbeat.now()
+    }
+
     pub fn get_rooms(&self) -> Vec<RoomItem> {
         let mut rooms = Vec::new();
-        let curr_user = self.inner.borrow().curr_user;
+        let now = self.now();
+        let inner = self.inner.borrow();
 
-        for room in self.inner.borrow().rooms.iter().rev() {
-            rooms.push(RoomItem::from_attr(room, curr_user));
+        for room in inner.rooms.values().rev() {
+            let draft = inner.drafts.get(&room.id);
+            rooms.push(RoomItem::from_attr(room, draft, inner.curr_user, &now));
         }
         rooms
     }
 
     pub fn search_rooms(&self, target: &String) -> Vec<RoomItem> {
         let mut rooms = Vec::new();
-        let curr_user = self.inner.borrow().curr_user;
+        let now = self.now();
+        let inner = self.inner.borrow();
 
-        for room in self.inner.borrow().rooms.iter().rev() {
-            let room_item = RoomItem::from_attr(room, curr_user);
+        for room in inner.rooms.values().rev() {
+            let draft = inner.drafts.get(&room.id);
+            let room_item = RoomItem::from_attr(room, draft, inner.curr_user, &now);
             if room_item.name.contains(target) {
                 rooms.push(room_item);
             }
@@ -141,101 +272,129 @@ impl ChatState {
         rooms
     }
 
-    pub fn get_messages(&self, room_id: i64) -> Vec<MessageItem> {
-        let mut messages = Vec::new();
-        let curr_user = self.inner.borrow().curr_user;
-        let now = Local::now();
-
-        if let Some(room) = self.inner.borrow().rooms.iter().find(|r| r.id == room_id) {
-            let mut seen = AttrValue::default();
+    /// Unread messages of every room not muted
+    pub fn count_unreads(&self, muted: &BTreeSet<i64>) -> usize {
+        self.inner
+            .borrow()
+            .rooms
+            .values()
+            .filter(|x| !muted.contains(&x.id))
+            .map(|x| x.unreads.max(0) as usize)
+            .sum()
+    }
 
-            for message in room.messages.iter() {
-                let span = time_ago(&message.send_at, &now);
-                if span != seen {
-                    seen = span.clone();
-                    messages.push(MessageItem::TimeDivider(span));
-                }
-                if message.sid == curr_user {
-                    messages.push(MessageItem::Outgoing(MessageContent::from_attr(message)));
-                } else {
-                    messages.push(MessageItem::Incoming(MessageContent::from_attr(message)));
-                }
-            }
+    /// Notification of the latest message of a room, unless it is muted, sent
+    /// by the current user, or already in sight
+    pub fn notice(&self, room_id: i64, muted: &BTreeSet<i64>, focused: bool) -> Option<Notice> {
+        let inner = self.inner.borrow();
+        if muted.contains(&room_id) || (focused && room_id == inner.curr_room) {
+            return None;
+        }
+        let room = inner.rooms.get(room_id)?;
+        if room.messages.last()?.sid == inner.curr_user {
+            return None;
         }
 
-        messages
+        let (name, cover) = parse_room_meta(room, inner.curr_user);
+        let (preview, _) = latest_message(room, &inner.heartbeat.now());
+        Some(Notice {
+            title: name.to_string(),
+            body: preview.to_string(),
+            icon: cover.to_string(),
+            tag: format!("room-{}", room_id),
+        })
     }
 
-    pub fn get_relations(&self) -> HashMap<i64, Relation> {
-        let mut map: HashMap<i64, Relation> = self
-            .inner
-            .borrow()
-            .friends
-            .iter()
-            .map(|item| (item.id, item.relation.clone()))
-            .collect();
+    /// Sound of the latest message of a room, unless it was sent by the current
+    /// user or is already in sight
+    pub fn message_cue(&self, room_id: i64, focused: bool) -> Option<Cue> {
+        let inner = self.inner.borrow();
+        if focused && room_id == inner.curr_room {
+            return None;
+        }
+        let room = inner.rooms.get(room_id)?;
+        let message = room.messages.last()?;
+        if message.sid == inner.curr_user {
+            return None;
+        }
 
-        let curr_user = self.inner.borrow().curr_user;
-        map.insert(curr_user, Relation::Yourself);
-        map
+        let me = room.members.iter().find(|x| x.id == inner.curr_user);
+        let mentioned = me.is_some_and(|me| {
+            let mention = format!("@{}", me.name.to_lowercase());
+            message.content.to_lowercase().contains(&mention)
+        });
+        Some(if mentioned {
+            Cue::Mention
+        } else {
+            Cue::Message
+        })
     }
 
-    pub fn get_members(&self, room_id: i64) -> Vec<MemberItem> {
-        let mut members = Vec::new();
-        let friends = self.get_relations();
+    /// Keys of every display item of a room, in the order of `get_messages`
+    pub fn message_keys(&self, room_id: i64) -> Vec<AttrValue> {
+        let mut inner = self.inner.borrow_mut();
+        let mut keys: Vec<_> = match inner.message_items(room_id) {
+            Some(items) => items.items.iter().map(MessageItem::key).collect(),
+            None => return Vec::new(),
+        };
+        let pending = inner.pending.iter().filter(|x| x.room_id == room_id);
+        keys.extend(pending.map(|x| AttrValue::from(x.nonce.to_string())));
+        keys
+    }
 
-        if let Some(room) = self.inner.borrow().rooms.iter().find(|r| r.id == room_id) {
-            for member in room.members.iter() {
-                let relation = friends
-                    .get(&member.id)
-                    .cloned()
-                    .unwrap_or(Relation::Stranger);
+    /// Display items of a room in the range, messages not acknowledged yet come last
+    pub fn get_messages(&self, room_id: i64, range: Range<usize>) -> Vec<MessageItem> {
+        let mut inner = self.inner.borrow_mut();
 
-                members.push(MemberItem::from_attr(member, relation))
+        let (mut messages, confirmed) = match inner.message_items(room_id) {
+            Some(items) => {
+                let len = items.items.len();
+                let window = &items.items[range.start.min(len)..range.end.min(len)];
+                (window.to_vec(), len)
             }
+            None => return Vec::new(),
+        };
+
+        if range.end > confirmed {
+            let curr_user = inner.curr_user;
+            let sender = inner
+                .rooms
+                .get(room_id)
+                .and_then(|room| room.members.iter().find(|x| x.id == curr_user));
+
+            let pending = inner
+                .pending
+                .iter()
+                .filter(|x| x.room_id == room_id)
+                .skip(range.start.saturating_sub(confirmed))
+                .take(range.end - range.start.max(confirmed))
+                .map(|message| MessageItem::Pending(PendingContent::from_attr(message, sender)));
+            messages.extend(pending);
         }
 
-        members
+        messages
     }
 
-    pub fn search_members(&self, room_id: i64, target: &String) -> Vec<MemberItem> {
-        let mut members = Vec::new();
-        let friends = self.get_relations();
-
-        if let Some(room) = self.inner.borrow().rooms.iter().find(|r| r.id == room_id) {
-            for member in room.members.iter() {
-                if member.name.contains(target) {
-                    let relation = friends
-                        .get(&member.id)
-                        .cloned()
-                        .unwrap_or(Relation::Stranger);
-
-                    members.push(MemberItem::from_attr(member, relation))
-                }
-            }
-        }
+    pub fn get_members(&self, room_id: i64) -> Rc<Vec<MemberItem>> {
+        self.inner.borrow_mut().member_items(room_id)
+    }
 
-        members
+    pub fn search_members(&self, room_id: i64, target: &String) -> Vec<MemberItem> {
+        self.get_members(room_id)
+            .iter()
+            .filter(|member| member.name.contains(target))
+            .cloned()
+            .collect()
     }
 
     pub fn get_curr_friend(&self, friend_id: i64) -> Option<FriendAttr> {
-        if let Some(friend) = self
-            .inner
-            .borrow()
-            .friends
-            .iter()
-            .find(|x| x.id == friend_id)
-        {
-            Some(friend.clone())
-        } else {
-            None
-        }
+        self.inner.borrow().friends.get(friend_id).cloned()
     }
 
     pub fn get_outgoing_friends(&self) -> Vec<FriendAttr> {
         let mut friends = Vec::new();
 
-        for friend in self.inner.borrow().friends.iter() {
+        for friend in self.inner.borrow().friends.values() {
             if let Relation::OutgoingAdding = friend.relation {
                 friends.push(friend.clone());
             }
@@ -247,7 +406,7 @@ impl ChatState {
     pub fn get_incoming_friends(&self) -> Vec<FriendAttr> {
         let mut friends = Vec::new();
 
-        for friend in self.inner.borrow().friends.iter() {
+        for friend in self.inner.borrow().friends.values() {
             if let Relation::IncomingAdding = friend.relation {
                 friends.push(friend.clone());
             }
@@ -259,7 +418,7 @@ impl ChatState {
     pub fn get_accepted_friends(&self) -> Vec<FriendItem> {
         let mut friends = Vec::new();
 
-        for friend in self.inner.borrow().friends.iter() {
+        for friend in self.inner.borrow().friends.values() {
             if let Relation::Friend(_) = friend.relation {
                 friends.push(FriendItem::from_attr(friend));
             }
@@ -271,7 +430,7 @@ impl ChatState {
     pub fn search_accepted_friends(&self, target: &String) -> Vec<FriendItem> {
         let mut friends = Vec::new();
 
-        for friend in self.inner.borrow().friends.iter() {
+        for friend in self.inner.borrow().friends.values() {
             if let Relation::Friend(_) = friend.relation {
                 if friend.username.contains(target) || friend.nickname.contains(target) {
                     friends.push(FriendItem::from_attr(friend));
@@ -283,10 +442,8 @@ impl ChatState {
     }
 
     pub fn get_friend_from_user(&self, resp: &GetUserByNameResponse) -> Option<FriendAttr> {
-        let friends = self.get_relations();
-
         if let Some(ref user) = resp.user {
-            let relation = friends.get(&user.id).cloned().unwrap_or(Relation::Stranger);
+            let relation = self.inner.borrow().relation(user.id);
             Some(FriendAttr::from_user(user, relation))
         } else {
             None
@@ -299,14 +456,419 @@ impl ChatState {
         if let Some(ref tx) = self.inner.borrow_mut().tx {
             let mut tx = tx.clone();
             spawn_local(async move {
-                tx.send(msg).await.unwrap_throw();
+                if tx.send(msg).await.is_err() {
+                    gloo_console::error!("The connection has been closed");
+                }
             })
         }
     }
 
-    pub fn send_message(&self, req: NewMessageRequest) {
-        let msg = ClientEvent::SendMessage(req).to_msg();
-        self.send(msg);
+    /// Text typed but not sent yet in a room
+    pub fn get_draft(&self, room_id: i64) -> String {
+        self.inner
+            .borrow()
+            .drafts
+            .get(&room_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Keep the text typed in a room, the room list is refreshed when a draft
+    /// appears or goes away
+    pub fn set_draft(&self, room_id: i64, text: String) {
+        let changed = {
+            let mut inner = self.inner.borrow_mut();
+            if text.is_empty() {
+                inner.drafts.remove(&room_id).is_some()
+            } else {
+                inner.drafts.insert(room_id, text).is_none()
+            }
+        };
+
+        if changed {
+            self.store.publish(&[Topic::Rooms]);
+        }
+    }
+
+    pub fn get_drafts(&self) -> HashMap<i64, String> {
+        self.inner.borrow().drafts.clone()
+    }
+
+    /// Show the message right away and send it, until the server echoes it back
+    pub fn send_message(&self, mut req: NewMessageRequest) {
+        let nonce = *req.nonce.get_or_insert_with(Uuid::new_v4);
+        let message = PendingAttr::from_request(&req, nonce);
+
+        let mut inner = self.inner.borrow_mut();
+        outbox::save(&message.to_outbox(inner.curr_user));
+        inner.pending.push(message);
+        drop(inner);
+
+        self.deliver(req, 0);
+    }
+
+    /// Whether the current user can run the command in a room
+    fn can_run(&self, room_id: i64, spec: &CommandSpec) -> bool {
+        let category = match self.inner.borrow().rooms.get(room_id) {
+            Some(room) => room.category.clone(),
+            None => return false,
+        };
+        spec.allowed(&category, &self.get_rank(room_id))
+    }
+
+    /// Commands, or users of a command, matching what is typed in the composer
+    pub fn complete_command(&self, room_id: i64, input: &str) -> Vec<Suggestion> {
+        match Completion::parse(input) {
+            Some(Completion::Name(prefix)) => COMMANDS
+                .iter()
+                .filter(|x| x.name.starts_with(prefix) && self.can_run(room_id, x))
+                .map(|spec| Suggestion {
+                    value: if spec.args.is_empty() {
+                        format!("/{}", spec.name).into()
+                    } else {
+                        format!("/{} ", spec.name).into()
+                    },
+                    label: spec.usage().into(),
+                    hint: i18n::t(spec.about).into(),
+                })
+                .collect(),
+            Some(Completion::User(spec, prefix)) if self.can_run(room_id, spec) => {
+                let prefix = prefix.to_lowercase();
+                self.command_users(room_id, spec.name)
+                    .into_iter()
+                    .filter(|(_, handle, name)| {
+                        handle.to_lowercase().starts_with(&prefix)
+                            || name.to_lowercase().starts_with(&prefix)
+                    })
+                    .take(MAX_SUGGESTIONS)
+                    .map(|(_, handle, name)| Suggestion {
+                        value: format!("/{} @{}", spec.name, handle).into(),
+                        label: format!("@{}", handle).into(),
+                        hint: name,
+                    })
+                    .collect()
+            }
+            Some(Completion::Arg(spec)) if self.can_run(room_id, spec) => vec![Suggestion {
+                value: input.to_owned().into(),
+                label: spec.usage().into(),
+                hint: i18n::t(spec.about).into(),
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Rooms matching a query of the quick switcher by their name, a friend
+    /// or a member, and friends without their room loaded, best first
+    pub fn quick_switch(&self, query: &str) -> Vec<SwitchItem> {
+        let query = query.trim().to_lowercase();
+        let inner = self.inner.borrow();
+        let mut items = Vec::new();
+
+        for (recency, room) in inner.rooms.values().rev().enumerate() {
+            let (name, cover) = parse_room_meta(room, inner.curr_user);
+            let mut candidates = vec![(name.clone(), AttrValue::default())];
+            let others = room.members.iter().filter(|x| x.id != inner.curr_user);
+            if room.category == "private" {
+                // the friend behind the room, by any of their names
+                if let Some(friend) = others.filter_map(|x| inner.friends.get(x.id)).next() {
+                    let hint =
+                        AttrValue::from(i18n::tr("switcher-friend", &[("name", &friend.username)]));
+                    candidates.push((friend.username.clone(), hint.clone()));
+                    candidates.push((friend.nickname.clone(), hint));
+                }
+            } else {
+                candidates.extend(others.map(|x| {
+                    let hint = i18n::tr("switcher-member", &[("name", &x.name)]);
+                    (x.name.clone(), hint.into())
+                }));
+            }
+
+            // the first of the best matches, the name before the others
+            let best = candidates
+                .into_iter()
+                .filter_map(|(text, hint)| match_score(&text, &query).map(|x| (x, hint)))
+                .reduce(|best, x| if x.0 > best.0 { x } else { best });
+            if let Some((score, hint)) = best {
+                let item = SwitchItem {
+                    room_id: room.id,
+                    name,
+                    cover,
+                    hint,
+                    unreads: room.unreads,
+                };
+                items.push((rank(score, recency), item));
+            }
+        }
+
+        // friends whose private room is not in the list, after all the rooms
+        let recency = inner.rooms.len();
+        for friend in inner.friends.values() {
+            let room_id = match friend.relation {
+                Relation::Friend(room_id) if room_id > 0 && !inner.rooms.contains(room_id) => {
+                    room_id
+                }
+                _ => continue,
+            };
+            let score = [&friend.username, &friend.nickname]
+                .into_iter()
+                .filter_map(|x| match_score(x, &query))
+                .max();
+            if let Some(score) = score {
+                let hint = i18n::tr("switcher-friend", &[("name", &friend.username)]);
+                let item = SwitchItem {
+                    room_id,
+                    name: friend.nickname.clone(),
+                    cover: friend.avatar.clone(),
+                    hint: hint.into(),
+                    unreads: 0,
+                };
+                items.push((rank(score, recency), item));
+            }
+        }
+
+        items.sort_by_key(|(rank, _)| std::cmp::Reverse(*rank));
+        items
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|(_, item)| item)
+            .collect()
+    }
+
+    /// Users a command can target: friends not in the room yet to invite,
+    /// the other members to kick, with the handle typed after @ and the name
+    /// shown. Only friends have a known username, other members go by id
+    fn command_users(&self, room_id: i64, name: &str) -> Vec<(i64, AttrValue, AttrValue)> {
+        let inner = self.inner.borrow();
+        let room = match inner.rooms.get(room_id) {
+            Some(room) => room,
+            None => return Vec::new(),
+        };
+
+        match name {
+            "invite" => inner
+                .friends
+                .values()
+                .filter(|x| matches!(x.relation, Relation::Friend(_)))
+                .filter(|x| room.members.iter().all(|member| member.id != x.id))
+                .map(|x| (x.id, x.username.clone(), x.nickname.clone()))
+                .collect(),
+            "kick" => room
+                .members
+                .iter()
+                .filter(|x| x.id != inner.curr_user && x.rank != "owner")
+                .map(|x| {
+                    let handle = match inner.friends.get(x.id) {
+                        Some(friend) => friend.username.clone(),
+                        None => x.id.to_string().into(),
+                    };
+                    (x.id, handle, x.name.clone())
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The user typed by username or by id
+    fn find_command_user(&self, room_id: i64, command: &str, name: &str) -> Option<i64> {
+        let id = name.parse::<i64>().ok();
+        self.command_users(room_id, command)
+            .into_iter()
+            .find(|(x, handle, _)| Some(*x) == id || handle.eq_ignore_ascii_case(name))
+            .map(|(id, _, _)| id)
+    }
+
+    /// Run a slash command in a room, or tell why it cannot be run
+    pub fn run_command(&self, room_id: i64, command: Command) -> Result<(), String> {
+        let name = command.name();
+        match CommandSpec::find(name) {
+            Some(spec) if self.can_run(room_id, spec) => {}
+            _ => return Err(i18n::tr("command-denied", &[("name", name)])),
+        }
+
+        match command {
+            Command::Rename(name) => {
+                let req = NewRoomNameResquest { room_id, name };
+                req.validate()
+                    .map_err(|e| validation_error(&e, "name", &i18n::t("name-subject")))?;
+                self.update_room_name(req);
+            }
+            Command::Invite(name) => match self.find_command_user(room_id, "invite", &name) {
+                Some(friend_id) => self.add_members(room_id, vec![friend_id]),
+                None => return Err(i18n::tr("command-no-friend", &[("name", &name)])),
+            },
+            Command::Kick(name) => match self.find_command_user(room_id, "kick", &name) {
+                Some(member_id) => {
+                    let member_ids = vec![member_id];
+                    self.delete_members(DeleteMembersRequest {
+                        room_id,
+                        member_ids,
+                    });
+                }
+                None => return Err(i18n::tr("command-no-member", &[("name", &name)])),
+            },
+            Command::Leave => self.leave_room(room_id),
+            Command::Me(action) => self.send_command_message(room_id, action, "action")?,
+            Command::Shrug(text) => {
+                let content = format!("{} ¯\\_(ツ)_/¯", text).trim_start().to_owned();
+                self.send_command_message(room_id, content, "text")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_command_message(
+        &self,
+        room_id: i64,
+        content: String,
+        kind: &str,
+    ) -> Result<(), String> {
+        let req = NewMessageRequest {
+            room_id,
+            content,
+            kind: kind.to_owned(),
+            nonce: None,
+        };
+        req.validate()
+            .map_err(|e| validation_error(&e, "content", &i18n::t("message-subject")))?;
+        self.send_message(req);
+        Ok(())
+    }
+
+    /// Show the cached state until the server snapshot arrives
+    pub fn restore_cache(&self, cached: CachedState) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            if inner.synced {
+                return;
+            }
+            let rooms = cached.rooms.into_iter().map(RoomAttr::from).collect();
+            let friends = cached.friends.into_iter().map(FriendAttr::from).collect();
+            inner.restore(rooms, friends);
+        }
+
+        self.store.publish_all();
+    }
+
+    /// Show the messages left in the outbox by a previous page, and send them
+    pub fn restore_queued(&self, items: Vec<OutboxItem>) {
+        let mut topics = Vec::new();
+        {
+            let mut inner = self.inner.borrow_mut();
+            for item in items {
+                if inner.pending.iter().all(|x| x.nonce != item.nonce) {
+                    topics.push(Topic::Messages(item.room_id));
+                    inner.pending.push(PendingAttr::from_outbox(item));
+                }
+            }
+        }
+
+        self.store.publish(&topics);
+        self.flush_queued();
+    }
+
+    /// Send the messages queued while the connection was down, in order
+    pub fn flush_queued(&self) {
+        let queued: Vec<(NewMessageRequest, u32)> = self
+            .inner
+            .borrow_mut()
+            .pending
+            .iter_mut()
+            .filter(|x| x.status == SendStatus::Queued)
+            .map(|message| {
+                message.status = SendStatus::Pending;
+                message.attempt += 1;
+                (message.to_request(), message.attempt)
+            })
+            .collect();
+
+        for (req, attempt) in queued {
+            self.deliver(req, attempt);
+        }
+    }
+
+    pub fn retry_message(&self, nonce: Uuid) {
+        let retry = self
+            .inner
+            .borrow_mut()
+            .pending
+            .iter_mut()
+            .find(|x| x.nonce == nonce && x.status == SendStatus::Failed)
+            .map(|message| {
+                message.status = SendStatus::Pending;
+                message.attempt += 1;
+                (message.to_request(), message.attempt)
+            });
+
+        if let Some((req, attempt)) = retry {
+            self.deliver(req, attempt);
+        }
+    }
+
+    pub fn discard_message(&self, nonce: Uuid) {
+        let room_id = {
+            let mut inner = self.inner.borrow_mut();
+            let idx = inner.pending.iter().position(|x| x.nonce == nonce);
+            idx.map(|idx| inner.pending.remove(idx).room_id)
+        };
+
+        if let Some(room_id) = room_id {
+            outbox::remove(self.inner.borrow().curr_user, room_id, &nonce);
+            self.notify_message(room_id);
+        }
+    }
+
+    fn deliver(&self, req: NewMessageRequest, attempt: u32) {
+        let room_id = req.room_id;
+        let nonce = req.nonce.unwrap_or_default();
+
+        let sent = match self.inner.borrow_mut().tx {
+            Some(ref mut tx) => tx.try_send(ClientEvent::SendMessage(req).to_msg()).is_ok(),
+            None => false,
+        };
+
+        let mut inner = self.inner.borrow_mut();
+        if let Some(message) = inner.pending.iter_mut().find(|x| x.nonce == nonce) {
+            if sent {
+                // the timer is kept by the message, it must not keep the state alive
+                let state = Rc::downgrade(&self.inner);
+                let store = self.store.clone();
+                let timer = Timeout::new(SEND_TIMEOUT, move || {
+                    if let Some(inner) = state.upgrade() {
+                        let store = store.clone();
+                        ChatState { inner, store }.fail_message(nonce, attempt);
+                    }
+                });
+                // the timer of an earlier try is cancelled
+                message.timer = Some(timer);
+            } else {
+                // sent again once the connection is back
+                message.status = SendStatus::Queued;
+                message.timer = None;
+            }
+        }
+        drop(inner);
+        self.notify_message(room_id);
+    }
+
+    fn fail_message(&self, nonce: Uuid, attempt: u32) {
+        let room_id = self
+            .inner
+            .borrow_mut()
+            .pending
+            .iter_mut()
+            .find(|x| x.nonce == nonce && x.attempt == attempt && x.status == SendStatus::Pending)
+            .map(|message| {
+                message.status = SendStatus::Failed;
+                message.room_id
+            });
+
+        if let Some(room_id) = room_id {
+            self.notify_message(room_id);
+        }
+    }
+
+    fn notify_message(&self, room_id: i64) {
+        self.store.publish(&[Topic::Messages(room_id)]);
     }
 
     pub fn get_user_rooms(&self) {
@@ -377,3 +939,169 @@ impl ChatState {
         self.send(msg);
     }
 }
+
+/// Whether the event changes the rooms, friends or messages kept in the cache,
+/// heartbeats and closes leave it as it is
+fn changes_cache(event: &ServerEvent) -> bool {
+    !matches!(event, ServerEvent::Pong(_) | ServerEvent::Close(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::types::MemberAttr;
+    use chrono::Utc;
+    use shared::{room::DeleteRoomResponse, session::PongResponse};
+
+    fn member(id: i64, name: &str, rank: &str) -> MemberAttr {
+        MemberAttr {
+            id,
+            name: name.to_owned().into(),
+            avatar: AttrValue::default(),
+            rank: rank.to_owned().into(),
+        }
+    }
+
+    fn friend(id: i64, username: &str, nickname: &str, room_id: i64) -> FriendAttr {
+        FriendAttr {
+            id,
+            username: username.to_owned().into(),
+            nickname: nickname.to_owned().into(),
+            avatar: AttrValue::default(),
+            bio: AttrValue::default(),
+            relation: Relation::Friend(room_id),
+        }
+    }
+
+    fn room(id: i64, name: &str, category: &str, members: Vec<MemberAttr>) -> RoomAttr {
+        RoomAttr {
+            id,
+            name: name.to_owned().into(),
+            cover: AttrValue::default(),
+            category: category.to_owned().into(),
+            unreads: 0,
+            first_unread: None,
+            members,
+            messages: Vec::new(),
+        }
+    }
+
+    // the current user 1 in a public room 7 with Bob, a friend, Carol and the owner
+    fn chat_as(rank: &str) -> ChatState {
+        let mut state = WebSocketState::new(1);
+        let members = vec![
+            member(1, "Me", rank),
+            member(2, "Bob", "member"),
+            member(3, "Olga", "owner"),
+            member(5, "Carol", "member"),
+        ];
+        state.rooms.insert(7, room(7, "Lounge", "public", members));
+        state.friends.insert(2, friend(2, "bobby", "Bob", 20));
+        state.friends.insert(4, friend(4, "alice", "Alice", 40));
+        ChatState {
+            inner: Rc::new(RefCell::new(state)),
+            store: Rc::new(Store::default()),
+        }
+    }
+
+    fn values(suggestions: Vec<Suggestion>) -> Vec<String> {
+        suggestions
+            .into_iter()
+            .map(|x| x.value.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn complete_names_by_rank() {
+        assert_eq!(
+            values(chat_as("member").complete_command(7, "/")),
+            ["/leave", "/me ", "/shrug "]
+        );
+        assert_eq!(
+            values(chat_as("owner").complete_command(7, "/")),
+            ["/rename ", "/invite ", "/kick ", "/me ", "/shrug "]
+        );
+        assert_eq!(
+            values(chat_as("manager").complete_command(7, "/ki")),
+            ["/kick "]
+        );
+        // no room, no command
+        assert!(chat_as("owner").complete_command(8, "/").is_empty());
+    }
+
+    #[test]
+    fn complete_users() {
+        let chat = chat_as("manager");
+        assert_eq!(
+            values(chat.complete_command(7, "/invite @")),
+            ["/invite @alice"]
+        );
+
+        // members by username if a friend, by id otherwise, never the owner
+        let kick = chat.complete_command(7, "/kick @");
+        let labels: Vec<_> = kick.iter().map(|x| x.label.to_string()).collect();
+        let hints: Vec<_> = kick.iter().map(|x| x.hint.to_string()).collect();
+        assert_eq!(labels, ["@bobby", "@5"]);
+        assert_eq!(hints, ["Bob", "Carol"]);
+
+        // by the handle or the name shown
+        assert_eq!(values(chat.complete_command(7, "/kick @car")), ["/kick @5"]);
+        assert_eq!(
+            values(chat.complete_command(7, "/kick @BO")),
+            ["/kick @bobby"]
+        );
+        assert!(chat_as("member").complete_command(7, "/kick @").is_empty());
+    }
+
+    #[test]
+    fn quick_switch_rooms_and_friends() {
+        let chat = chat_as("member");
+        {
+            let mut inner = chat.inner.borrow_mut();
+            let mut private = room(20, "", "private", vec![member(1, "Me", "member")]);
+            private.members.push(member(2, "Bob", "member"));
+            inner.rooms.insert(20, private);
+            // no room for Alice is loaded
+        }
+
+        let names = |query: &str| -> Vec<(i64, String)> {
+            chat.quick_switch(query)
+                .into_iter()
+                .map(|x| (x.room_id, x.name.to_string()))
+                .collect()
+        };
+        assert_eq!(names("lounge"), [(7, "Lounge".to_owned())]);
+        // by a member of the room
+        assert_eq!(names("carol"), [(7, "Lounge".to_owned())]);
+        // the friend by their username, with the private room
+        assert_eq!(names("bobby")[0].0, 20);
+        // a friend without the room loaded, opened by the id of their room
+        assert_eq!(names("alice"), [(40, "Alice".to_owned())]);
+        assert!(names("zzz").is_empty());
+    }
+
+    #[test]
+    fn find_users_by_username_or_id() {
+        let chat = chat_as("owner");
+        assert_eq!(chat.find_command_user(7, "kick", "BOBBY"), Some(2));
+        assert_eq!(chat.find_command_user(7, "kick", "5"), Some(5));
+        // not by the name shown, nor the owner
+        assert_eq!(chat.find_command_user(7, "kick", "Carol"), None);
+        assert_eq!(chat.find_command_user(7, "kick", "3"), None);
+        assert_eq!(chat.find_command_user(7, "invite", "alice"), Some(4));
+        assert_eq!(chat.find_command_user(7, "invite", "bobby"), None);
+    }
+
+    #[test]
+    fn only_state_changes_write_the_cache() {
+        let pong = ServerEvent::Pong(PongResponse {
+            nonce: 1,
+            server_time: Utc::now(),
+        });
+        assert!(!changes_cache(&pong));
+        assert!(!changes_cache(&ServerEvent::Close(CloseReason::Expired)));
+        assert!(changes_cache(&ServerEvent::DeletedRoom(
+            DeleteRoomResponse { room_id: 7 }
+        )));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::i18n;
+
+// the most suggestions shown at once
+pub const MAX_SUGGESTIONS: usize = 8;
+
+// ========================// CommandSpec //======================== //
+
+/// A slash command of the composer, with who can run it
+pub struct CommandSpec {
+    pub name: &'static str,
+    // placeholder of the argument, empty if it takes none
+    pub args: &'static str,
+    // key of the description in the locales
+    pub about: &'static str,
+    // only makes sense in a public room
+    pub public: bool,
+    // ranks allowed to run it, anyone if empty
+    pub ranks: &'static [&'static str],
+}
+
+pub const COMMANDS: [CommandSpec; 6] = [
+    CommandSpec {
+        name: "rename",
+        args: "<name>",
+        about: "command-rename",
+        public: true,
+        ranks: &["owner", "manager"],
+    },
+    CommandSpec {
+        name: "invite",
+        args: "@user",
+        about: "command-invite",
+        public: true,
+        ranks: &["owner", "manager"],
+    },
+    CommandSpec {
+        name: "kick",
+        args: "@user",
+        about: "command-kick",
+        public: true,
+        ranks: &["owner", "manager"],
+    },
+    CommandSpec {
+        name: "leave",
+        args: "",
+        about: "command-leave",
+        public: true,
+        ranks: &["manager", "member"],
+    },
+    CommandSpec {
+        name: "me",
+        args: "<action>",
+        about: "command-me",
+        public: false,
+        ranks: &[],
+    },
+    CommandSpec {
+        name: "shrug",
+        args: "[message]",
+        about: "command-shrug",
+        public: false,
+        ranks: &[],
+    },
+];
+
+impl CommandSpec {
+    pub fn find(name: &str) -> Option<&'static CommandSpec> {
+        COMMANDS.iter().find(|x| x.name == name)
+    }
+
+    /// Whether a member of the rank can run it in a room of the category
+    pub fn allowed(&self, category: &str, rank: &str) -> bool {
+        (!self.public || category == "public")
+            && (self.ranks.is_empty() || self.ranks.contains(&rank))
+    }
+
+    pub fn usage(&self) -> String {
+        if self.args.is_empty() {
+            format!("/{}", self.name)
+        } else {
+            format!("/{} {}", self.name, self.args)
+        }
+    }
+}
+
+// ========================// Command //======================== //
+
+/// A parsed slash command, users are still named as typed
+pub enum Command {
+    Rename(String),
+    Invite(String),
+    Kick(String),
+    Leave,
+    Me(String),
+    Shrug(String),
+}
+
+impl Command {
+    /// Parse the text of the composer, None if it is not a command
+    pub fn parse(input: &str) -> Option<Result<Self, String>> {
+        let input = input.trim().strip_prefix('/')?;
+        let (name, arg) = match input.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (input, ""),
+        };
+
+        let spec = match CommandSpec::find(name) {
+            Some(spec) => spec,
+            None => return Some(Err(i18n::tr("command-unknown", &[("name", name)]))),
+        };
+        let user = arg.strip_prefix('@').unwrap_or(arg).trim();
+
+        let command = match spec.name {
+            "rename" if !arg.is_empty() => Self::Rename(arg.to_owned()),
+            "invite" if !user.is_empty() => Self::Invite(user.to_owned()),
+            "kick" if !user.is_empty() => Self::Kick(user.to_owned()),
+            "leave" => Self::Leave,
+            "me" if !arg.is_empty() => Self::Me(arg.to_owned()),
+            "shrug" => Self::Shrug(arg.to_owned()),
+            _ => {
+                let usage = spec.usage();
+                return Some(Err(i18n::tr("command-usage", &[("usage", &usage)])));
+            }
+        };
+        Some(Ok(command))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rename(_) => "rename",
+            Self::Invite(_) => "invite",
+            Self::Kick(_) => "kick",
+            Self::Leave => "leave",
+            Self::Me(_) => "me",
+            Self::Shrug(_) => "shrug",
+        }
+    }
+}
+
+// ========================// Completion //======================== //
+
+/// What is being typed after the slash
+pub enum Completion<'a> {
+    // the name of a command
+    Name(&'a str),
+    // a user after @ for a command taking one
+    User(&'static CommandSpec, &'a str),
+    // any other argument
+    Arg(&'static CommandSpec),
+}
+
+impl<'a> Completion<'a> {
+    pub fn parse(input: &'a str) -> Option<Self> {
+        let input = input.strip_prefix('/')?;
+        match input.split_once(char::is_whitespace) {
+            None => Some(Self::Name(input)),
+            Some((name, arg)) => {
+                let spec = CommandSpec::find(name)?;
+                let arg = arg.trim_start();
+                if spec.args == "@user" {
+                    Some(Self::User(spec, arg.strip_prefix('@').unwrap_or(arg)))
+                } else {
+                    // the hint goes away once the argument is typed
+                    arg.is_empty().then_some(Self::Arg(spec))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_commands() {
+        assert!(Command::parse("hello").is_none());
+        assert!(matches!(
+            Command::parse("  /rename  The lounge "),
+            Some(Ok(Command::Rename(x))) if x == "The lounge"
+        ));
+        assert!(matches!(
+            Command::parse("/invite @alice"),
+            Some(Ok(Command::Invite(x))) if x == "alice"
+        ));
+        assert!(matches!(
+            Command::parse("/kick 42"),
+            Some(Ok(Command::Kick(x))) if x == "42"
+        ));
+        assert!(matches!(Command::parse("/leave"), Some(Ok(Command::Leave))));
+        assert!(matches!(
+            Command::parse("/me waves"),
+            Some(Ok(Command::Me(x))) if x == "waves"
+        ));
+        assert!(matches!(
+            Command::parse("/shrug"),
+            Some(Ok(Command::Shrug(x))) if x.is_empty()
+        ));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(Command::parse("/dance"), Some(Err(x)) if x.contains("/dance")));
+        // the argument is required
+        assert!(matches!(Command::parse("/rename"), Some(Err(x)) if x.contains("/rename <name>")));
+        assert!(matches!(Command::parse("/kick @"), Some(Err(x)) if x.contains("/kick @user")));
+        assert!(matches!(Command::parse("/me  "), Some(Err(_))));
+    }
+
+    #[test]
+    fn complete_what_is_typed() {
+        assert!(Completion::parse("hi /me").is_none());
+        assert!(matches!(Completion::parse("/"), Some(Completion::Name(""))));
+        assert!(matches!(
+            Completion::parse("/ki"),
+            Some(Completion::Name("ki"))
+        ));
+        assert!(matches!(
+            Completion::parse("/kick @al"),
+            Some(Completion::User(spec, "al")) if spec.name == "kick"
+        ));
+        assert!(matches!(
+            Completion::parse("/invite bo"),
+            Some(Completion::User(spec, "bo")) if spec.name == "invite"
+        ));
+        assert!(matches!(
+            Completion::parse("/rename "),
+            Some(Completion::Arg(_))
+        ));
+        // the hint goes away once the argument is typed
+        assert!(Completion::parse("/rename x").is_none());
+        assert!(Completion::parse("/dance x").is_none());
+    }
+
+    #[test]
+    fn command_rights() {
+        let rename = CommandSpec::find("rename").unwrap();
+        assert!(rename.allowed("public", "owner"));
+        assert!(!rename.allowed("public", "member"));
+        assert!(!rename.allowed("private", "owner"));
+
+        let me = CommandSpec::find("me").unwrap();
+        assert!(me.allowed("private", "member"));
+        assert_eq!(me.usage(), "/me <action>");
+        assert_eq!(CommandSpec::find("leave").unwrap().usage(), "/leave");
+    }
+}
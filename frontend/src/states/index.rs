@@ -0,0 +1,218 @@
+use std::collections::{btree_map, BTreeMap, HashMap};
+
+// ========================// OrderedMap //======================== //
+
+/// Items keyed by id, iterated in the order they were last inserted or
+/// touched, so lookups, updates and moves to the end do not scan the list
+pub struct OrderedMap<T> {
+    // item by id, with its position in `order`
+    items: HashMap<i64, (u64, T)>,
+    // id by position, the most recent last
+    order: BTreeMap<u64, i64>,
+    next: u64,
+}
+
+impl<T> Default for OrderedMap<T> {
+    fn default() -> Self {
+        Self {
+            items: HashMap::new(),
+            order: BTreeMap::new(),
+            next: 0,
+        }
+    }
+}
+
+impl<T> OrderedMap<T> {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, id: i64) -> bool {
+        self.items.contains_key(&id)
+    }
+
+    pub fn get(&self, id: i64) -> Option<&T> {
+        self.items.get(&id).map(|(_, item)| item)
+    }
+
+    /// Change an item in place, keeping its position
+    pub fn get_mut(&mut self, id: i64) -> Option<&mut T> {
+        self.items.get_mut(&id).map(|(_, item)| item)
+    }
+
+    /// Insert an item as the most recent one, replacing any item with the same id
+    pub fn insert(&mut self, id: i64, item: T) {
+        let pos = self.bump();
+        if let Some((old, _)) = self.items.insert(id, (pos, item)) {
+            self.order.remove(&old);
+        }
+        self.order.insert(pos, id);
+    }
+
+    /// Move an item to the end, returns false if there is no such item
+    pub fn touch(&mut self, id: i64) -> bool {
+        let pos = self.bump();
+        match self.items.get_mut(&id) {
+            Some((old, _)) => {
+                self.order.remove(old);
+                self.order.insert(pos, id);
+                *old = pos;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove(&mut self, id: i64) -> Option<T> {
+        let (pos, item) = self.items.remove(&id)?;
+        self.order.remove(&pos);
+        Some(item)
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+        let order = &mut self.order;
+        self.items.retain(|_, (pos, item)| {
+            let keep = f(item);
+            if !keep {
+                order.remove(pos);
+            }
+            keep
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.order.clear();
+    }
+
+    /// Items from the oldest to the most recent
+    pub fn values(&self) -> Values<'_, T> {
+        Values {
+            order: self.order.values(),
+            items: &self.items,
+        }
+    }
+
+    /// Every item in no particular order
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.items.values_mut().map(|(_, item)| item)
+    }
+
+    fn bump(&mut self) -> u64 {
+        self.next += 1;
+        self.next
+    }
+}
+
+impl<T> FromIterator<(i64, T)> for OrderedMap<T> {
+    fn from_iter<I: IntoIterator<Item = (i64, T)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<T> Extend<(i64, T)> for OrderedMap<T> {
+    fn extend<I: IntoIterator<Item = (i64, T)>>(&mut self, iter: I) {
+        for (id, item) in iter {
+            self.insert(id, item);
+        }
+    }
+}
+
+// ---------------- Values ---------------- //
+pub struct Values<'a, T> {
+    order: btree_map::Values<'a, u64, i64>,
+    items: &'a HashMap<i64, (u64, T)>,
+}
+
+impl<'a, T> Iterator for Values<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.order.next()?;
+        self.items.get(id).map(|(_, item)| item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Values<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let id = self.order.next_back()?;
+        self.items.get(id).map(|(_, item)| item)
+    }
+}
+
+impl<T> ExactSizeIterator for Values<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(map: &OrderedMap<&'static str>) -> Vec<&'static str> {
+        map.values().copied().collect()
+    }
+
+    #[test]
+    fn iterates_in_insertion_order() {
+        let map: OrderedMap<_> = [(3, "c"), (1, "a"), (2, "b")].into_iter().collect();
+        assert_eq!(ids(&map), ["c", "a", "b"]);
+        assert_eq!(
+            map.values().rev().copied().collect::<Vec<_>>(),
+            ["b", "a", "c"]
+        );
+        assert_eq!(map.values().len(), 3);
+    }
+
+    #[test]
+    fn insert_replaces_and_moves_to_end() {
+        let mut map: OrderedMap<_> = [(1, "a"), (2, "b")].into_iter().collect();
+        map.insert(1, "x");
+        assert_eq!(map.len(), 2);
+        assert_eq!(ids(&map), ["b", "x"]);
+    }
+
+    #[test]
+    fn touch_moves_to_end() {
+        let mut map: OrderedMap<_> = [(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+        assert!(map.touch(1));
+        assert_eq!(ids(&map), ["b", "c", "a"]);
+        assert!(map.touch(1));
+        assert_eq!(ids(&map), ["b", "c", "a"]);
+        assert!(!map.touch(4));
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn get_mut_keeps_position() {
+        let mut map: OrderedMap<_> = [(1, "a"), (2, "b")].into_iter().collect();
+        *map.get_mut(1).unwrap() = "x";
+        assert_eq!(ids(&map), ["x", "b"]);
+    }
+
+    #[test]
+    fn remove_and_retain() {
+        let mut map: OrderedMap<_> = [(1, "a"), (2, "b"), (3, "c"), (4, "d")]
+            .into_iter()
+            .collect();
+        assert_eq!(map.remove(2), Some("b"));
+        assert_eq!(map.remove(2), None);
+        assert!(!map.contains(2));
+        assert_eq!(ids(&map), ["a", "c", "d"]);
+
+        map.retain(|x| *x != "c");
+        assert_eq!(ids(&map), ["a", "d"]);
+        assert!(!map.touch(3));
+
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.values().next(), None);
+    }
+}
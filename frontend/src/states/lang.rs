@@ -0,0 +1,56 @@
+use crate::i18n::{self, Lang};
+use gloo_storage::{LocalStorage, Storage};
+use std::rc::Rc;
+use yew::{Reducible, UseReducerHandle};
+
+const LANG: &str = "lang";
+
+// ========================// LangState //======================== //
+
+/// Language of the UI, picked by the user or else by the browser
+#[derive(Clone, PartialEq)]
+pub struct LangState {
+    pub lang: Lang,
+}
+
+pub enum LangAction {
+    Set(Lang),
+}
+
+impl LangState {
+    pub fn load() -> Self {
+        let lang = LocalStorage::get(LANG).unwrap_or_else(|_| Lang::detect());
+        apply(lang);
+        Self { lang }
+    }
+}
+
+impl Reducible for LangState {
+    type Action = LangAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            LangAction::Set(lang) if lang != self.lang => {
+                if let Err(e) = LocalStorage::set(LANG, lang) {
+                    gloo_console::error!("Failed to save the language:", e.to_string());
+                }
+                apply(lang);
+                Self { lang }.into()
+            }
+            LangAction::Set(_) => self,
+        }
+    }
+}
+
+// the text written outside of the components follows it, as do screen readers
+fn apply(lang: Lang) {
+    i18n::set_lang(lang);
+    let root = web_sys::window()
+        .and_then(|x| x.document())
+        .and_then(|x| x.document_element());
+    if let Some(root) = root {
+        let _ = root.set_attribute("lang", lang.code());
+    }
+}
+
+pub type LangContext = UseReducerHandle<LangState>;
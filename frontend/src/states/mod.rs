@@ -1,8 +1,12 @@
 pub mod auth;
 pub mod chat;
-pub mod friend;
-pub mod room;
+pub mod command;
+pub mod index;
+pub mod lang;
+pub mod settings;
+pub mod store;
+pub mod switcher;
+pub mod theme;
 pub mod toast;
-pub mod trigger;
 pub mod types;
 pub mod websocket;
@@ -0,0 +1,116 @@
+use crate::sound;
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+use yew::{Reducible, UseReducerHandle};
+
+// ========================// SettingsState //======================== //
+
+/// Preferences of a user on this browser, kept in the local storage
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsState {
+    // user the settings belong to, none before anyone logs in
+    #[serde(skip)]
+    pub user_id: Option<i64>,
+    // show a desktop notification for the messages received
+    pub notifications: bool,
+    // rooms that neither notify, sound nor count in the unread badge
+    pub muted: BTreeSet<i64>,
+    // sound pack of the cues, or "off"
+    pub sound_pack: String,
+    // volume of the cues, from 0 to 100
+    pub volume: u8,
+    // sound pack of a room in place of the one above
+    pub room_sounds: BTreeMap<i64, String>,
+}
+
+impl Default for SettingsState {
+    fn default() -> Self {
+        Self {
+            user_id: None,
+            notifications: false,
+            muted: BTreeSet::new(),
+            sound_pack: sound::PACKS[0].0.to_owned(),
+            volume: 60,
+            room_sounds: BTreeMap::new(),
+        }
+    }
+}
+
+pub enum SettingsAction {
+    // switch to the settings of the user logged in
+    Load(i64),
+    Notifications(bool),
+    Mute(i64, bool),
+    SoundPack(String),
+    Volume(u8),
+    // None follows the sound pack of the settings again
+    RoomSound(i64, Option<String>),
+}
+
+impl SettingsState {
+    pub fn load(user_id: i64) -> Self {
+        let settings: Self = LocalStorage::get(settings_key(user_id)).unwrap_or_default();
+        Self {
+            user_id: Some(user_id),
+            ..settings
+        }
+    }
+
+    pub fn is_muted(&self, room_id: i64) -> bool {
+        self.muted.contains(&room_id)
+    }
+
+    /// Sound pack of the cues of a room
+    pub fn room_sound(&self, room_id: i64) -> &str {
+        self.room_sounds
+            .get(&room_id)
+            .unwrap_or(&self.sound_pack)
+            .as_str()
+    }
+}
+
+impl Reducible for SettingsState {
+    type Action = SettingsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut settings = (*self).clone();
+        match action {
+            SettingsAction::Load(user_id) => return Self::load(user_id).into(),
+            SettingsAction::Notifications(enabled) => settings.notifications = enabled,
+            SettingsAction::Mute(room_id, true) => {
+                settings.muted.insert(room_id);
+            }
+            SettingsAction::Mute(room_id, false) => {
+                settings.muted.remove(&room_id);
+            }
+            SettingsAction::SoundPack(pack) => settings.sound_pack = pack,
+            SettingsAction::Volume(volume) => settings.volume = volume.min(100),
+            SettingsAction::RoomSound(room_id, Some(pack)) => {
+                settings.room_sounds.insert(room_id, pack);
+            }
+            SettingsAction::RoomSound(room_id, None) => {
+                settings.room_sounds.remove(&room_id);
+            }
+        }
+
+        // saved as it changes, the next page starts with it
+        if let Some(user_id) = settings.user_id {
+            if let Err(e) = LocalStorage::set(settings_key(user_id), &settings) {
+                gloo_console::error!("Failed to save the settings:", e.to_string());
+            }
+        }
+        settings.into()
+    }
+}
+
+/// Local storage key of the settings of a user
+fn settings_key(user_id: i64) -> String {
+    format!("settings-{}", user_id)
+}
+
+pub type SettingsContext = UseReducerHandle<SettingsState>;
@@ -0,0 +1,121 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+use yew::Callback;
+
+// ========================// Topic //======================== //
+
+/// Part of the chat state a component can watch
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    // order, names, covers, unreads and latest message of every room
+    Rooms,
+    // name, cover and description of one room
+    Room(i64),
+    // sent and pending messages of one room
+    Messages(i64),
+    // members of one room and the rank of the current user
+    Members(i64),
+    // every friend and friend request, with their relations
+    Friends,
+    // profile of one friend
+    Friend(i64),
+    // a room was opened from outside the room list, like from a notification
+    Focus,
+}
+
+// ========================// Store //======================== //
+
+// callbacks of the components watching a topic, with their subscription id
+type Subscribers = Vec<(u64, Callback<()>)>;
+
+/// Components subscribe to the topics they render, and the chat state
+/// publishes the topics it changes, so only those components rerender
+#[derive(Default)]
+pub struct Store {
+    next_id: Cell<u64>,
+    subscribers: RefCell<HashMap<Topic, Subscribers>>,
+}
+
+impl Store {
+    pub fn subscribe(self: &Rc<Self>, topics: &[Topic], callback: Callback<()>) -> Subscription {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        let mut subscribers = self.subscribers.borrow_mut();
+        for topic in topics {
+            subscribers
+                .entry(*topic)
+                .or_default()
+                .push((id, callback.clone()));
+        }
+
+        Subscription {
+            store: Rc::downgrade(self),
+            id,
+            topics: topics.to_vec(),
+        }
+    }
+
+    /// Notify the subscribers of any of the topics, once each
+    pub fn publish(&self, topics: &[Topic]) {
+        let callbacks = {
+            let subscribers = self.subscribers.borrow();
+            let callbacks = topics
+                .iter()
+                .filter_map(|topic| subscribers.get(topic))
+                .flatten();
+            Self::dedup(callbacks)
+        };
+        callbacks.iter().for_each(|x| x.emit(()));
+    }
+
+    /// Notify every subscriber, when the whole state has been replaced
+    pub fn publish_all(&self) {
+        let callbacks = Self::dedup(self.subscribers.borrow().values().flatten());
+        callbacks.iter().for_each(|x| x.emit(()));
+    }
+
+    // a component watching several topics rerenders once
+    fn dedup<'a>(callbacks: impl Iterator<Item = &'a (u64, Callback<()>)>) -> Vec<Callback<()>> {
+        let mut ids = Vec::new();
+        let mut unique = Vec::new();
+        for (id, callback) in callbacks {
+            if !ids.contains(id) {
+                ids.push(*id);
+                unique.push(callback.clone());
+            }
+        }
+        unique
+    }
+
+    fn unsubscribe(&self, id: u64, topics: &[Topic]) {
+        let mut subscribers = self.subscribers.borrow_mut();
+        for topic in topics {
+            if let Some(list) = subscribers.get_mut(topic) {
+                list.retain(|(x, _)| *x != id);
+                if list.is_empty() {
+                    subscribers.remove(topic);
+                }
+            }
+        }
+    }
+}
+
+// ---------------- Subscription ---------------- //
+/// Unsubscribes when dropped
+pub struct Subscription {
+    store: Weak<Store>,
+    id: u64,
+    topics: Vec<Topic>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(store) = self.store.upgrade() {
+            store.unsubscribe(self.id, &self.topics);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// the most results of the quick switcher shown at once
+pub const MAX_RESULTS: usize = 8;
+
+// rooms further down the list than this are all as old to the ranking
+const RECENCY_SPAN: usize = 150;
+
+// ========================// Matching //======================== //
+
+/// How well a text matches a query in lower case, higher is better, `None`
+/// if the letters of the query are not all in the text in order
+pub fn match_score(text: &str, query: &str) -> Option<i64> {
+    let text = text.to_lowercase();
+    if query.is_empty() {
+        return Some(0);
+    }
+    if text == query {
+        return Some(1000);
+    }
+    if text.starts_with(query) {
+        return Some(800);
+    }
+    if let Some(pos) = text.find(query) {
+        // the start of a word of the text
+        let word = text[..pos]
+            .chars()
+            .last()
+            .is_some_and(|x| !x.is_alphanumeric());
+        return Some(if word { 600 } else { 400 });
+    }
+
+    // the letters spread over the text, the closer the better
+    let mut letters = query.chars().peekable();
+    let mut started = false;
+    let mut gaps = 0;
+    for c in text.chars() {
+        match letters.peek() {
+            None => break,
+            Some(&x) if x == c => {
+                letters.next();
+                started = true;
+            }
+            Some(_) if started => gaps += 1,
+            Some(_) => {}
+        }
+    }
+    letters.peek().is_none().then(|| (200 - gaps).max(50))
+}
+
+/// Rank of a match in the room at the position in the room list, the rooms
+/// talked in lately come first among matches of the same quality
+pub fn rank(score: i64, recency: usize) -> i64 {
+    score - recency.min(RECENCY_SPAN) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn better_matches_score_higher() {
+        assert_eq!(match_score("Lounge", "lounge"), Some(1000));
+        assert_eq!(match_score("Lounge bar", "lou"), Some(800));
+        assert_eq!(match_score("The lounge", "lou"), Some(600));
+        assert_eq!(match_score("Glounge", "lou"), Some(400));
+        // two letters skipped between the first and the last
+        assert_eq!(match_score("Lounge", "lnge"), Some(198));
+        assert_eq!(match_score("Lounge", "egnuol"), None);
+        assert_eq!(match_score("Lounge", "loungex"), None);
+        // everything matches an empty query
+        assert_eq!(match_score("Lounge", ""), Some(0));
+    }
+
+    #[test]
+    fn spread_letters_score_lower() {
+        let close = match_score("abcdef", "af").unwrap();
+        let far = match_score(&format!("a{}f", "x".repeat(500)), "af").unwrap();
+        assert!(close > far);
+        assert_eq!(far, 50);
+        assert!(close < match_score("xaf", "af").unwrap());
+    }
+
+    #[test]
+    fn recent_rooms_rank_first() {
+        assert!(rank(800, 0) > rank(800, 3));
+        assert_eq!(rank(800, 3), 797);
+        // old rooms are all as old
+        assert_eq!(rank(800, RECENCY_SPAN), rank(800, RECENCY_SPAN * 4));
+        // a better match wins over recency
+        assert!(rank(600, RECENCY_SPAN) > rank(400, 0));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::hooks::UseLocalStorageHandle;
+use serde::{Deserialize, Serialize};
+
+/// Local storage key of the theme
+pub const THEME: &str = "theme";
+
+/// Backgrounds of the chat page, by name and CSS image
+pub const BACKGROUNDS: [(&str, &str); 4] = [
+    ("photo", "url('/assets/pic/chat-bg.jpg')"),
+    (
+        "aurora",
+        "linear-gradient(135deg, #0ea5e9 0%, #6366f1 50%, #a855f7 100%)",
+    ),
+    (
+        "dusk",
+        "linear-gradient(160deg, #f97316 0%, #e11d48 45%, #312e81 100%)",
+    ),
+    // the color of the theme only
+    ("plain", "none"),
+];
+
+// ========================// ThemeMode //======================== //
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    // light or dark as the browser prefers
+    #[default]
+    System,
+    // light with stronger text and borders
+    Contrast,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 4] = [Self::Light, Self::Dark, Self::System, Self::Contrast];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::System => "system",
+            Self::Contrast => "contrast",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.name() == name)
+    }
+
+    /// Palette applied to the page, as in its `data-theme` attribute
+    pub fn resolve(&self, prefers_dark: bool) -> &'static str {
+        match self {
+            Self::System if prefers_dark => Self::Dark.name(),
+            Self::System => Self::Light.name(),
+            _ => self.name(),
+        }
+    }
+}
+
+// ========================// ThemeState //======================== //
+
+/// Look of this browser, kept in the local storage
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeState {
+    pub mode: ThemeMode,
+    // name of the background of the chat page
+    pub background: String,
+}
+
+impl Default for ThemeState {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::default(),
+            background: BACKGROUNDS[0].0.to_owned(),
+        }
+    }
+}
+
+impl ThemeState {
+    /// Inline style of the chat page, unknown backgrounds fall back to the first
+    pub fn background_style(&self) -> String {
+        let image = BACKGROUNDS
+            .iter()
+            .find(|(name, _)| *name == self.background)
+            .unwrap_or(&BACKGROUNDS[0])
+            .1;
+        format!("background-image: {}", image)
+    }
+}
+
+pub type ThemeContext = UseLocalStorageHandle<ThemeState>;
@@ -1,10 +1,18 @@
-use chrono::{DateTime, Datelike, Local, Utc};
+use crate::{db::outbox::OutboxItem, i18n};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
 use gloo_net::websocket::Message;
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
 use shared::{
-    event::ClientEvent, friend::FriendInfo, member::MemberInfo, message::MessageInfo,
-    room::RoomInfo, user::UserInfo,
+    event::ClientEvent,
+    friend::FriendInfo,
+    member::MemberInfo,
+    message::{MessageInfo, NewMessageRequest},
+    room::RoomInfo,
+    user::UserInfo,
 };
 use std::cmp::Ordering;
+use uuid::Uuid;
 use wasm_bindgen::UnwrapThrowExt;
 use yew::AttrValue;
 
@@ -23,7 +31,7 @@ impl ConvertToMessage for ClientEvent {
 }
 
 // ---------------- Relation ---------------- //
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
 pub enum Relation {
     Yourself,
     Friend(i64),
@@ -81,10 +89,18 @@ impl FriendAttr {
             username: user.username.clone().into(),
             nickname: user.nickname.clone().into(),
             avatar: user.avatar.clone().into(),
-            bio: user.avatar.clone().into(),
+            bio: user.bio.clone().into(),
             relation,
         }
     }
+
+    /// Refresh the profile fields from updated user info
+    pub fn update(&mut self, user: &UserInfo) {
+        self.username = user.username.clone().into();
+        self.nickname = user.nickname.clone().into();
+        self.avatar = user.avatar.clone().into();
+        self.bio = user.bio.clone().into();
+    }
 }
 
 // ---------------- MessageAttr ---------------- //
@@ -113,6 +129,79 @@ impl From<MessageInfo> for MessageAttr {
     }
 }
 
+// ---------------- PendingAttr ---------------- //
+#[derive(Clone, Copy, PartialEq)]
+pub enum SendStatus {
+    // waiting for the server to echo the message
+    Pending,
+    // waiting for the connection, kept in the outbox meanwhile
+    Queued,
+    // timed out or could not be sent, the user may retry or discard it
+    Failed,
+}
+
+/// Message sent by the current user and not acknowledged by the server yet
+pub struct PendingAttr {
+    pub nonce: Uuid,
+    pub room_id: i64,
+    pub content: AttrValue,
+    pub kind: AttrValue,
+    pub status: SendStatus,
+    // bumped on every retry, so that the timeout of an earlier try is ignored
+    pub attempt: u32,
+    pub create_at: DateTime<Utc>,
+    // fails the message unless acknowledged in time, cancelled once dropped
+    pub timer: Option<Timeout>,
+}
+
+impl PendingAttr {
+    pub fn from_request(req: &NewMessageRequest, nonce: Uuid) -> Self {
+        Self {
+            nonce,
+            room_id: req.room_id,
+            content: req.content.clone().into(),
+            kind: req.kind.clone().into(),
+            status: SendStatus::Pending,
+            attempt: 0,
+            create_at: Utc::now(),
+            timer: None,
+        }
+    }
+
+    pub fn from_outbox(item: OutboxItem) -> Self {
+        Self {
+            nonce: item.nonce,
+            room_id: item.room_id,
+            content: item.content.into(),
+            kind: item.kind.into(),
+            status: SendStatus::Queued,
+            attempt: 0,
+            create_at: item.create_at,
+            timer: None,
+        }
+    }
+
+    pub fn to_outbox(&self, user_id: i64) -> OutboxItem {
+        OutboxItem {
+            user_id,
+            room_id: self.room_id,
+            nonce: self.nonce,
+            content: self.content.to_string(),
+            kind: self.kind.to_string(),
+            create_at: self.create_at,
+        }
+    }
+
+    pub fn to_request(&self) -> NewMessageRequest {
+        NewMessageRequest {
+            room_id: self.room_id,
+            content: self.content.to_string(),
+            kind: self.kind.to_string(),
+            nonce: Some(self.nonce),
+        }
+    }
+}
+
 // ---------------- MemberAttr ---------------- //
 pub struct MemberAttr {
     pub id: i64,
@@ -132,6 +221,14 @@ impl From<MemberInfo> for MemberAttr {
     }
 }
 
+impl MemberAttr {
+    /// Refresh the display fields from updated user info
+    pub fn update(&mut self, user: &UserInfo) {
+        self.name = user.nickname.clone().into();
+        self.avatar = user.avatar.clone().into();
+    }
+}
+
 // ---------------- RoomAttr ---------------- //
 pub struct RoomAttr {
     pub id: i64,
@@ -139,6 +236,8 @@ pub struct RoomAttr {
     pub cover: AttrValue,
     pub category: AttrValue,
     pub unreads: i64,
+    // id of the first message unread when the room was opened
+    pub first_unread: Option<i64>,
     pub members: Vec<MemberAttr>,
     pub messages: Vec<MessageAttr>,
 }
@@ -151,6 +250,7 @@ impl From<RoomInfo> for RoomAttr {
             cover: room.cover.into(),
             category: room.category.into(),
             unreads: 0,
+            first_unread: None,
             members: room.members.into_iter().map(MemberAttr::from).collect(),
             messages: room.messages.into_iter().map(MessageAttr::from).collect(),
         }
@@ -168,12 +268,19 @@ pub struct RoomItem {
     pub unreads: i64,
     pub latest_msg: AttrValue,
     pub latest_time: AttrValue,
+    // text typed but not sent yet
+    pub draft: Option<AttrValue>,
 }
 
 impl RoomItem {
-    pub fn from_attr(room: &RoomAttr, curr_user: i64) -> Self {
-        let (name, cover) = parse_room_meta(&room, curr_user);
-        let (latest_msg, latest_time) = latest_message(&room);
+    pub fn from_attr(
+        room: &RoomAttr,
+        draft: Option<&String>,
+        curr_user: i64,
+        now: &DateTime<Local>,
+    ) -> Self {
+        let (name, cover) = parse_room_meta(room, curr_user);
+        let (latest_msg, latest_time) = latest_message(room, now);
         Self {
             id: room.id,
             name,
@@ -181,10 +288,33 @@ impl RoomItem {
             unreads: room.unreads,
             latest_msg,
             latest_time,
+            draft: draft.map(|x| AttrValue::from(x.clone())),
         }
     }
 }
 
+// ---------------- Suggestion ---------------- //
+/// Completion offered while typing a slash command
+#[derive(Clone, PartialEq)]
+pub struct Suggestion {
+    // text of the composer once picked
+    pub value: AttrValue,
+    pub label: AttrValue,
+    pub hint: AttrValue,
+}
+
+// ---------------- SwitchItem ---------------- //
+/// Room offered by the quick switcher
+#[derive(Clone, PartialEq)]
+pub struct SwitchItem {
+    pub room_id: i64,
+    pub name: AttrValue,
+    pub cover: AttrValue,
+    // the friend or member matched instead of the name, empty if none
+    pub hint: AttrValue,
+    pub unreads: i64,
+}
+
 // ---------------- CurrRoomItem ---------------- //
 #[derive(Clone, PartialEq)]
 pub struct PublicRoom {
@@ -200,7 +330,7 @@ impl PublicRoom {
             .members
             .iter()
             .find(|x| x.id == curr_user)
-            .map(|x| x.rank.clone().into())
+            .map(|x| x.rank.clone())
             .unwrap_or(AttrValue::from("member"));
 
         Self {
@@ -226,7 +356,7 @@ impl PersonalRoom {
             id: room.id,
             name: room.name.clone(),
             cover: room.cover.clone(),
-            desc: AttrValue::from("Blank"),
+            desc: i18n::t("room-blank").into(),
         }
     }
 }
@@ -282,16 +412,46 @@ impl MessageContent {
     }
 }
 
+#[derive(Clone, PartialEq)]
+pub struct PendingContent {
+    pub nonce: Uuid,
+    pub name: AttrValue,
+    pub avatar: AttrValue,
+    pub content: AttrValue,
+    pub kind: AttrValue,
+    pub status: SendStatus,
+}
+
+impl PendingContent {
+    pub fn from_attr(message: &PendingAttr, sender: Option<&MemberAttr>) -> Self {
+        Self {
+            nonce: message.nonce,
+            name: sender.map(|x| x.name.clone()).unwrap_or_default(),
+            avatar: sender.map(|x| x.avatar.clone()).unwrap_or_default(),
+            content: message.content.clone(),
+            kind: message.kind.clone(),
+            status: message.status,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum MessageItem {
     TimeDivider(AttrValue),
+    // where the messages unread when the room was opened start
+    UnreadDivider,
     Incoming(MessageContent),
     Outgoing(MessageContent),
+    Pending(PendingContent),
 }
 
 impl PartialEq for MessageItem {
     fn eq(&self, other: &Self) -> bool {
-        self.key() == other.key()
+        match (self, other) {
+            // the status of a pending message changes under the same key
+            (MessageItem::Pending(a), MessageItem::Pending(b)) => a == b,
+            _ => self.key() == other.key(),
+        }
     }
 }
 
@@ -299,12 +459,63 @@ impl MessageItem {
     pub fn key(&self) -> AttrValue {
         match self {
             MessageItem::TimeDivider(s) => s.clone(),
+            MessageItem::UnreadDivider => "unread".into(),
             MessageItem::Incoming(content) => format!("{}", content.id).into(),
             MessageItem::Outgoing(content) => format!("{}", content.id).into(),
+            MessageItem::Pending(content) => content.nonce.to_string().into(),
         }
     }
 }
 
+// ---------------- MessageItems ---------------- //
+/// Display items of the messages of a room, extended as messages arrive
+pub struct MessageItems {
+    pub items: Vec<MessageItem>,
+    // divider of the last message
+    last_span: AttrValue,
+    // local day the dividers were written for, "Today" is stale the day after
+    date: NaiveDate,
+    // language the dividers were written in
+    lang: i18n::Lang,
+}
+
+impl MessageItems {
+    pub fn build(room: &RoomAttr, curr_user: i64, now: &DateTime<Local>) -> Self {
+        let mut items = Self {
+            items: Vec::with_capacity(room.messages.len() + 8),
+            last_span: AttrValue::default(),
+            date: now.date_naive(),
+            lang: i18n::lang(),
+        };
+        for message in room.messages.iter() {
+            if room.first_unread == Some(message.id) {
+                items.items.push(MessageItem::UnreadDivider);
+            }
+            items.push(message, curr_user, now);
+        }
+        items
+    }
+
+    pub fn push(&mut self, message: &MessageAttr, curr_user: i64, now: &DateTime<Local>) {
+        let span = time_ago(&message.send_at, now);
+        if span != self.last_span {
+            self.last_span = span.clone();
+            self.items.push(MessageItem::TimeDivider(span));
+        }
+        if message.sid == curr_user {
+            self.items
+                .push(MessageItem::Outgoing(MessageContent::from_attr(message)));
+        } else {
+            self.items
+                .push(MessageItem::Incoming(MessageContent::from_attr(message)));
+        }
+    }
+
+    pub fn is_stale(&self, now: &DateTime<Local>) -> bool {
+        self.date != now.date_naive() || self.lang != i18n::lang()
+    }
+}
+
 // ---------------- MemberItem ---------------- //
 
 #[derive(Clone, PartialEq)]
@@ -354,20 +565,26 @@ pub fn time_local(time: &DateTime<Utc>) -> AttrValue {
     time.format("%H:%M").to_string().into()
 }
 
-/// Display elapsed time
+/// Display local date
+pub fn time_date(time: &DateTime<Utc>) -> AttrValue {
+    let time = time.with_timezone(&Local);
+    i18n::format_date(&time).into()
+}
+
+/// Display elapsed time in the current language
 pub fn time_ago(time: &DateTime<Utc>, now: &DateTime<Local>) -> AttrValue {
     let time = time.with_timezone(&Local);
 
     if now.year() - time.year() > 0 {
-        time.format("%-d %B, %Y").to_string().into()
+        i18n::format_day_year(&time).into()
     } else if now.month() - time.month() > 0 {
-        time.format("%A, %-d %B").to_string().into()
+        i18n::format_day(&time).into()
     } else {
         let days = now.day() - time.day();
         match days {
-            0 => "Today".into(),
-            1 => "Yesterday".into(),
-            _ => time.format("%A, %-d %B").to_string().into(),
+            0 => i18n::t("today").into(),
+            1 => i18n::t("yesterday").into(),
+            _ => i18n::format_day(&time).into(),
         }
     }
 }
@@ -384,7 +601,7 @@ pub fn time_ago_short(time: &DateTime<Utc>, now: &DateTime<Local>) -> AttrValue
         let days = now.day() - time.day();
         match days {
             0 => time.format("%H:%M").to_string().into(),
-            1 => "Yesterday".into(),
+            1 => i18n::t("yesterday").into(),
             _ => time.format("%m-%d").to_string().into(),
         }
     }
@@ -421,18 +638,132 @@ pub fn parse_room_meta(room: &RoomAttr, curr_user: i64) -> (AttrValue, AttrValue
 }
 
 /// Return the latest message (content, time) of the room
-pub fn latest_message(room: &RoomAttr) -> (AttrValue, AttrValue) {
+pub fn latest_message(room: &RoomAttr, now: &DateTime<Local>) -> (AttrValue, AttrValue) {
     if let Some(msg) = room.messages.last() {
         let content = match room.category.as_str() {
+            _ if msg.kind == "action" => format!("{} {}", msg.name, msg.content).into(),
             "public" => format!("{}: {}", msg.name, msg.content).into(),
             _ => msg.content.clone(),
         };
 
-        let now = Local::now();
-        let time = time_ago_short(&msg.send_at, &now);
+        let time = time_ago_short(&msg.send_at, now);
 
         (content, time)
     } else {
         ("".into(), "".into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::websocket::WebSocketState;
+    use chrono::{Duration, TimeZone};
+
+    fn message(id: i64, sid: i64, send_at: DateTime<Local>) -> MessageAttr {
+        MessageAttr {
+            id,
+            sid,
+            name: AttrValue::default(),
+            avatar: AttrValue::default(),
+            content: AttrValue::default(),
+            kind: "text".into(),
+            send_at: send_at.with_timezone(&Utc),
+        }
+    }
+
+    // messages 1 to 4 of a room, two of them sent the day before
+    fn room(now: DateTime<Local>, first_unread: Option<i64>) -> RoomAttr {
+        let yesterday = now - Duration::days(1);
+        RoomAttr {
+            id: 7,
+            name: "Lounge".into(),
+            cover: AttrValue::default(),
+            category: "public".into(),
+            unreads: 0,
+            first_unread,
+            members: Vec::new(),
+            messages: vec![
+                message(1, 2, yesterday),
+                message(2, 1, yesterday + Duration::minutes(1)),
+                message(3, 2, now - Duration::minutes(2)),
+                message(4, 2, now - Duration::minutes(1)),
+            ],
+        }
+    }
+
+    fn keys(items: &MessageItems) -> Vec<String> {
+        items.items.iter().map(|x| x.key().to_string()).collect()
+    }
+
+    fn noon() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 5, 10, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn no_divider_when_all_read() {
+        let items = MessageItems::build(&room(noon(), None), 1, &noon());
+        assert_eq!(keys(&items), ["Yesterday", "1", "2", "Today", "3", "4"]);
+        assert!(matches!(items.items[2], MessageItem::Outgoing(_)));
+        assert!(matches!(items.items[1], MessageItem::Incoming(_)));
+    }
+
+    #[test]
+    fn divider_before_the_first_unread() {
+        let items = MessageItems::build(&room(noon(), Some(4)), 1, &noon());
+        assert_eq!(
+            keys(&items),
+            ["Yesterday", "1", "2", "Today", "3", "unread", "4"]
+        );
+
+        // before the day of the first unread message
+        let items = MessageItems::build(&room(noon(), Some(3)), 1, &noon());
+        assert_eq!(
+            keys(&items),
+            ["Yesterday", "1", "2", "unread", "Today", "3", "4"]
+        );
+
+        // a message not in the room shows none
+        let items = MessageItems::build(&room(noon(), Some(9)), 1, &noon());
+        assert!(!keys(&items).contains(&"unread".to_owned()));
+    }
+
+    #[test]
+    fn divider_stays_as_messages_arrive() {
+        let mut items = MessageItems::build(&room(noon(), Some(4)), 1, &noon());
+        items.push(&message(5, 2, noon()), 1, &noon());
+        assert_eq!(
+            keys(&items),
+            ["Yesterday", "1", "2", "Today", "3", "unread", "4", "5"]
+        );
+    }
+
+    #[test]
+    fn opening_a_room_places_the_divider() {
+        let now = Local::now();
+        let mut state = WebSocketState::new(1);
+        let mut unread = room(now, None);
+        unread.unreads = 2;
+        state.rooms.insert(7, unread);
+
+        // the dividers of the day are left out, they change at midnight
+        let messages = |state: &mut WebSocketState| -> Vec<String> {
+            let items = state.message_items(7).unwrap();
+            keys(items)
+                .into_iter()
+                .zip(items.items.iter())
+                .filter(|(_, x)| !matches!(x, MessageItem::TimeDivider(_)))
+                .map(|(key, _)| key)
+                .collect()
+        };
+        assert_eq!(messages(&mut state), ["1", "2", "3", "4"]);
+
+        assert!(state.open_room(7));
+        assert_eq!(state.rooms.get(7).unwrap().unreads, 0);
+        assert_eq!(messages(&mut state), ["1", "2", "unread", "3", "4"]);
+
+        // opened again with nothing unread, the divider goes away
+        assert!(!state.open_room(7));
+        assert_eq!(messages(&mut state), ["1", "2", "3", "4"]);
+    }
+}
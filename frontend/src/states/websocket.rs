@@ -1,186 +1,407 @@
 use super::{
-    trigger::{AffectedFriend, AffectedRoom, TriggerAction, Trigger},
-    types::{FriendAttr, MemberAttr, RoomAttr, cmp_room},
+    index::OrderedMap,
+    store::{Store, Topic},
+    types::{
+        cmp_room, FriendAttr, MemberAttr, MemberItem, MessageItems, PendingAttr, Relation, RoomAttr,
+    },
 };
-
+use crate::{db::outbox, notify};
+use chrono::{DateTime, Duration, Local, Utc};
 use futures::channel::mpsc;
 use gloo_net::websocket::Message;
-use shared::event::ServerEvent;
+use gloo_timers::callback::Timeout;
+use shared::{
+    event::{ServerEvent, ServerMessage},
+    friend::FriendInfo,
+    room::RoomInfo,
+    session::{CloseReason, PingRequest, PongResponse},
+};
+use std::{collections::HashMap, rc::Rc};
+
+// ========================// ConnStatus //======================== //
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConnStatus {
+    Connecting,
+    Connected,
+    // the connection dropped and will be opened again soon
+    Reconnecting,
+    // waiting for the browser to get back online
+    Offline,
+}
+
+// ========================// Heartbeat //======================== //
+
+#[derive(Default)]
+pub struct Heartbeat {
+    nonce: u64,
+    // the ping waiting for its pong, and when it was sent
+    pending: Option<(u64, DateTime<Utc>)>,
+    // pings left unanswered in a row
+    pub missed: u32,
+    // round-trip time of the last ping, in milliseconds
+    pub latency: Option<i64>,
+    // server clock minus local clock, in milliseconds
+    pub clock_skew: i64,
+}
+
+impl Heartbeat {
+    /// Start over for a new connection, keeping the last measurements
+    pub fn reset(&mut self) {
+        self.pending = None;
+        self.missed = 0;
+    }
+
+    pub fn ping(&mut self) -> PingRequest {
+        if self.pending.is_some() {
+            self.missed += 1;
+        }
+        self.nonce += 1;
+        self.pending = Some((self.nonce, Utc::now()));
+        PingRequest { nonce: self.nonce }
+    }
+
+    pub fn pong(&mut self, resp: &PongResponse) {
+        if let Some((nonce, sent_at)) = self.pending {
+            if nonce == resp.nonce {
+                let now = Utc::now();
+                let latency = (now - sent_at).num_milliseconds();
+                // assume the server answered halfway through the round trip
+                let local_time = sent_at + Duration::milliseconds(latency / 2);
+
+                self.latency = Some(latency);
+                self.clock_skew = (resp.server_time - local_time).num_milliseconds();
+                self.pending = None;
+                self.missed = 0;
+            }
+        }
+    }
+
+    /// Current time corrected by the clock skew to the server
+    pub fn now(&self) -> DateTime<Local> {
+        (Utc::now() + Duration::milliseconds(self.clock_skew)).with_timezone(&Local)
+    }
+}
 
 // ========================// WebSocketState //======================== //
 
 pub struct WebSocketState {
-    pub rooms: Vec<RoomAttr>,
-    pub friends: Vec<FriendAttr>,
+    // rooms by id, the most recently active last
+    pub rooms: OrderedMap<RoomAttr>,
+    // friends and friend requests by id, the latest last
+    pub friends: OrderedMap<FriendAttr>,
+    // members of each room with their relations, built when first shown
+    member_cache: HashMap<i64, Rc<Vec<MemberItem>>>,
+    // display items of the messages of each room, built when first shown
+    // and extended as messages arrive
+    message_cache: HashMap<i64, MessageItems>,
+    // messages sent by the current user and waiting for the server, kept across snapshots
+    pub pending: Vec<PendingAttr>,
+    // text typed but not sent yet in each room
+    pub drafts: HashMap<i64, String>,
     pub curr_room: i64,
     pub curr_friend: i64,
     pub curr_user: i64,
+    // sequence number of the last event applied, used to resume after reconnecting
+    pub last_seq: Option<u64>,
+    // a server snapshot has been applied, the cached state is stale from then on
+    pub synced: bool,
+    pub heartbeat: Heartbeat,
     pub tx: Option<mpsc::Sender<Message>>,
+    // pending write of the cache, replaced by every new event
+    pub cache_timer: Option<Timeout>,
 }
 
 impl WebSocketState {
     pub fn new(curr_user: i64) -> Self {
         Self {
-            rooms: Vec::new(),
-            friends: Vec::new(),
+            rooms: OrderedMap::default(),
+            friends: OrderedMap::default(),
+            member_cache: HashMap::new(),
+            message_cache: HashMap::new(),
+            pending: Vec::new(),
+            drafts: HashMap::new(),
             curr_room: 0,
             curr_friend: 0,
             curr_user,
+            last_seq: None,
+            synced: false,
+            heartbeat: Heartbeat::default(),
             tx: None,
+            cache_timer: None,
         }
     }
 
-    pub fn handle_event(&mut self, event: ServerEvent, trigger: Trigger) -> Result<(), String> {
-        match event {
-            ServerEvent::Close(msg) => {
-                return Err(msg);
+    /// Relation of the current user to another user
+    pub fn relation(&self, user_id: i64) -> Relation {
+        if user_id == self.curr_user {
+            return Relation::Yourself;
+        }
+        self.friends
+            .get(user_id)
+            .map(|x| x.relation.clone())
+            .unwrap_or(Relation::Stranger)
+    }
+
+    /// Members of a room with their relations, cached until the members or friends change
+    pub fn member_items(&mut self, room_id: i64) -> Rc<Vec<MemberItem>> {
+        if let Some(items) = self.member_cache.get(&room_id) {
+            return items.clone();
+        }
+
+        let items: Vec<MemberItem> = match self.rooms.get(room_id) {
+            Some(room) => room
+                .members
+                .iter()
+                .map(|member| MemberItem::from_attr(member, self.relation(member.id)))
+                .collect(),
+            None => return Rc::default(),
+        };
+
+        let items = Rc::new(items);
+        self.member_cache.insert(room_id, items.clone());
+        items
+    }
+
+    /// Display items of the messages of a room, rebuilt only when the day or
+    /// the language changes
+    pub fn message_items(&mut self, room_id: i64) -> Option<&MessageItems> {
+        let now = self.heartbeat.now();
+        let room = self.rooms.get(room_id)?;
+
+        let stale = match self.message_cache.get(&room_id) {
+            Some(items) => items.is_stale(&now),
+            None => true,
+        };
+        if stale {
+            let items = MessageItems::build(room, self.curr_user, &now);
+            self.message_cache.insert(room_id, items);
+        }
+        self.message_cache.get(&room_id)
+    }
+
+    /// Make a room the current one and mark its messages as read,
+    /// returns whether it had unread messages
+    pub fn open_room(&mut self, room_id: i64) -> bool {
+        self.curr_room = room_id;
+        let room = match self.rooms.get_mut(room_id) {
+            Some(room) => room,
+            None => return false,
+        };
+
+        let first_unread = match room.unreads as usize {
+            0 => None,
+            n => room.messages.iter().rev().nth(n - 1).map(|x| x.id),
+        };
+        if room.first_unread != first_unread {
+            room.first_unread = first_unread;
+            // the divider moves, so the items are built again
+            self.message_cache.remove(&room_id);
+        }
+
+        let had_unreads = room.unreads > 0;
+        room.unreads = 0;
+        had_unreads
+    }
+
+    /// Replace the rooms and friends by the ones kept from an earlier session
+    pub fn restore(&mut self, rooms: Vec<RoomAttr>, friends: Vec<FriendAttr>) {
+        self.rooms = rooms.into_iter().map(|x| (x.id, x)).collect();
+        self.friends = friends.into_iter().map(|x| (x.id, x)).collect();
+        self.member_cache.clear();
+        self.message_cache.clear();
+    }
+
+    /// Replace the rooms, ordered by their latest message
+    pub fn set_rooms(&mut self, mut rooms: Vec<RoomInfo>) {
+        rooms.sort_by(cmp_room);
+        self.rooms.clear();
+        self.rooms.extend(
+            rooms
+                .into_iter()
+                .map(|room| (room.id, RoomAttr::from(room))),
+        );
+        self.member_cache.clear();
+        self.message_cache.clear();
+    }
+
+    pub fn set_friends(&mut self, friends: Vec<FriendInfo>) {
+        self.friends.clear();
+        self.friends
+            .extend(friends.into_iter().map(|x| (x.id, FriendAttr::from(x))));
+        self.member_cache.clear();
+    }
+
+    pub fn handle_message(&mut self, msg: ServerMessage, store: &Store) -> Result<(), CloseReason> {
+        if let Some(seq) = msg.seq {
+            match msg.event {
+                // a snapshot replaces everything seen before
+                ServerEvent::Initialized(_) => {}
+                // already applied before the connection dropped
+                _ if self.last_seq.is_some_and(|last| seq <= last) => return Ok(()),
+                _ => {}
             }
-            ServerEvent::Initialized(mut resp) => {
-                resp.rooms.sort_by(cmp_room);
-                let mut rooms: Vec<RoomAttr> = resp.rooms.into_iter().map(RoomAttr::from).collect();
-                self.rooms.clear();
-                self.rooms.append(&mut rooms);
+            self.last_seq = Some(seq);
+        }
+        self.handle_event(msg.event, store)
+    }
 
-                let mut friends: Vec<FriendAttr> =
-                    resp.friends.into_iter().map(FriendAttr::from).collect();
-                self.friends.clear();
-                self.friends.append(&mut friends);
+    pub fn handle_event(&mut self, event: ServerEvent, store: &Store) -> Result<(), CloseReason> {
+        match event {
+            ServerEvent::Close(reason) => {
+                return Err(reason);
+            }
+            ServerEvent::Pong(resp) => {
+                self.heartbeat.pong(&resp);
+            }
+            ServerEvent::Initialized(resp) => {
+                self.set_rooms(resp.rooms);
+                self.set_friends(resp.friends);
+                self.synced = true;
+                // a room opened by a link before the rooms arrived is read now
+                self.open_room(self.curr_room);
 
-                trigger.dispatch(TriggerAction::Init);
+                store.publish_all();
             }
             ServerEvent::ReceiveMessage(resp) => {
-                if let Some(idx) = self.rooms.iter().position(|room| room.id == resp.room_id) {
-                    let mut room = self.rooms.remove(idx);
-                    room.messages.push(resp.message.into());
-                    room.unreads += 1;
-
-                    let aroom = AffectedRoom {
-                        room_id: room.id,
-                        curr_room: self.curr_room,
-                    };
-
-                    self.rooms.push(room);
-                    trigger.dispatch(TriggerAction::Message(aroom));
+                if let Some(nonce) = resp.nonce {
+                    self.pending.retain(|x| x.nonce != nonce);
+                    // the server got the message, it no longer needs to be kept
+                    outbox::remove(self.curr_user, resp.room_id, &nonce);
                 }
-            }
-            ServerEvent::UserRooms(mut resp) => {
-                resp.rooms.sort_by(cmp_room);
-                let mut rooms: Vec<RoomAttr> = resp.rooms.into_iter().map(RoomAttr::from).collect();
+                if let Some(room) = self.rooms.get_mut(resp.room_id) {
+                    let message = resp.message.into();
+                    if let Some(items) = self.message_cache.get_mut(&resp.room_id) {
+                        items.push(&message, self.curr_user, &self.heartbeat.now());
+                    }
+                    room.messages.push(message);
+                    // messages of the open room are read as they arrive, unless
+                    // the user is away, then they are once the page is back
+                    if resp.room_id != self.curr_room || !notify::has_focus() {
+                        room.unreads += 1;
+                    }
 
-                self.rooms.clear();
-                self.rooms.append(&mut rooms);
+                    self.rooms.touch(resp.room_id);
+                    store.publish(&[Topic::Rooms, Topic::Messages(resp.room_id)]);
+                }
+            }
+            ServerEvent::UserRooms(resp) => {
+                self.set_rooms(resp.rooms);
+                // a room opened before it was fetched is read now
+                self.open_room(self.curr_room);
 
-                trigger.dispatch(TriggerAction::Init);
+                store.publish_all();
             }
             ServerEvent::JoinedRoom(resp) => {
-                let aroom = AffectedRoom {
-                    room_id: resp.room.id,
-                    curr_room: self.curr_room,
-                };
+                let room_id = resp.room.id;
 
-                self.rooms.push(resp.room.into());
-                trigger.dispatch(TriggerAction::Room(aroom));
+                self.rooms.insert(room_id, resp.room.into());
+                self.member_cache.remove(&room_id);
+                self.message_cache.remove(&room_id);
+                store.publish(&room_topics(room_id));
             }
             ServerEvent::DeletedRoom(resp) => {
-                if let Some(idx) = self.rooms.iter().position(|room| room.id == resp.room_id) {
-                    self.rooms.remove(idx);
-
-                    let aroom = AffectedRoom {
-                        room_id: resp.room_id,
-                        curr_room: self.curr_room,
-                    };
-                    trigger.dispatch(TriggerAction::Room(aroom));
+                if self.rooms.remove(resp.room_id).is_some() {
+                    self.member_cache.remove(&resp.room_id);
+                    self.message_cache.remove(&resp.room_id);
+
+                    store.publish(&room_topics(resp.room_id));
                 }
             }
             ServerEvent::UpdatedRoomName(resp) => {
-                if let Some(room) = self.rooms.iter_mut().find(|room| room.id == resp.room_id) {
+                if let Some(room) = self.rooms.get_mut(resp.room_id) {
                     room.name = resp.name.into();
 
-                    let aroom = AffectedRoom {
-                        room_id: resp.room_id,
-                        curr_room: self.curr_room,
-                    };
-                    trigger.dispatch(TriggerAction::Room(aroom));
+                    store.publish(&[Topic::Rooms, Topic::Room(resp.room_id)]);
                 }
             }
             ServerEvent::LeavedRoom(resp) => {
-                self.rooms.retain(|x| x.id != resp.room_id);
+                self.rooms.remove(resp.room_id);
+                self.member_cache.remove(&resp.room_id);
+                self.message_cache.remove(&resp.room_id);
 
-                let aroom = AffectedRoom {
-                    room_id: resp.room_id,
-                    curr_room: self.curr_room,
-                };
-                trigger.dispatch(TriggerAction::Room(aroom));
+                store.publish(&room_topics(resp.room_id));
             }
             ServerEvent::AddedRoomMembers(resp) => {
-                if let Some(room) = self.rooms.iter_mut().find(|room| room.id == resp.room_id) {
+                if let Some(room) = self.rooms.get_mut(resp.room_id) {
                     let mut members: Vec<MemberAttr> =
                         resp.members.into_iter().map(MemberAttr::from).collect();
                     room.members.append(&mut members);
+                    self.member_cache.remove(&resp.room_id);
 
-                    let aroom = AffectedRoom {
-                        room_id: resp.room_id,
-                        curr_room: self.curr_room,
-                    };
-                    trigger.dispatch(TriggerAction::Member(aroom));
+                    store.publish(&[Topic::Room(resp.room_id), Topic::Members(resp.room_id)]);
                 }
             }
             ServerEvent::DeletedRoomMembers(resp) => {
-                if let Some(room) = self.rooms.iter_mut().find(|room| room.id == resp.room_id) {
+                if let Some(room) = self.rooms.get_mut(resp.room_id) {
                     room.members.retain(|x| !resp.member_ids.contains(&x.id));
+                    self.member_cache.remove(&resp.room_id);
 
-                    let aroom = AffectedRoom {
-                        room_id: resp.room_id,
-                        curr_room: self.curr_room,
-                    };
-                    trigger.dispatch(TriggerAction::Member(aroom));
+                    store.publish(&[Topic::Room(resp.room_id), Topic::Members(resp.room_id)]);
                 }
             }
             ServerEvent::UserFriends(resp) => {
-                let mut friends: Vec<FriendAttr> =
-                    resp.friends.into_iter().map(FriendAttr::from).collect();
-                self.friends.clear();
-                self.friends.append(&mut friends);
+                self.set_friends(resp.friends);
 
-                trigger.dispatch(TriggerAction::Init);
+                store.publish_all();
             }
             ServerEvent::AddFriend(resp) => {
-                self.friends.push(resp.friend.into());
+                self.friends.insert(resp.friend.id, resp.friend.into());
+                // relations shown in the member lists have changed
+                self.member_cache.clear();
 
-                trigger.dispatch(TriggerAction::NewFriend);
+                store.publish(&[Topic::Friends]);
             }
             ServerEvent::AcceptedFriend(resp) => {
-                if let Some(idx) = self.friends.iter().position(|x| x.id == resp.friend.id) {
-                    self.friends.swap_remove(idx);
-                }
-
-                let afriend = AffectedFriend {
-                    friend_id: resp.friend.id,
-                    curr_friend: self.curr_friend,
-                };
+                let friend_id = resp.friend.id;
 
-                self.friends.push(resp.friend.into());
-                trigger.dispatch(TriggerAction::Friend(afriend));
+                self.friends.insert(friend_id, resp.friend.into());
+                self.member_cache.clear();
+                store.publish(&[Topic::Friends, Topic::Friend(friend_id)]);
             }
             ServerEvent::RefusedFriend(resp) => {
-                if let Some(idx) = self.friends.iter().position(|x| x.id == resp.friend_id) {
-                    self.friends.swap_remove(idx);
+                if self.friends.remove(resp.friend_id).is_some() {
+                    self.member_cache.clear();
 
-                    trigger.dispatch(TriggerAction::NewFriend);
+                    store.publish(&[Topic::Friends, Topic::Friend(resp.friend_id)]);
                 }
             }
             ServerEvent::DeletedFriend(resp) => {
-                if let Some(idx) = self.friends.iter().position(|x| x.id == resp.friend_id) {
-                    self.friends.swap_remove(idx);
-
-                    let afriend = AffectedFriend {
-                        friend_id: resp.friend_id,
-                        curr_friend: self.curr_friend,
-                    };
-                    trigger.dispatch(TriggerAction::Friend(afriend));
+                if self.friends.remove(resp.friend_id).is_some() {
+                    self.member_cache.clear();
+
+                    store.publish(&[Topic::Friends, Topic::Friend(resp.friend_id)]);
+                }
+            }
+            ServerEvent::UserUpdated(resp) => {
+                let user = resp.user;
+
+                if let Some(friend) = self.friends.get_mut(user.id) {
+                    friend.update(&user);
                 }
+                for room in self.rooms.values_mut() {
+                    if let Some(member) = room.members.iter_mut().find(|x| x.id == user.id) {
+                        member.update(&user);
+                        // the messages show the name and avatar of the sender
+                        self.message_cache.remove(&room.id);
+                    }
+                }
+                self.member_cache.clear();
+
+                // the user may appear in any room or friend list
+                store.publish_all();
             }
         }
         Ok(())
     }
 }
+
+// everything shown about a room, when it appears or goes away
+fn room_topics(room_id: i64) -> [Topic; 4] {
+    [
+        Topic::Rooms,
+        Topic::Room(room_id),
+        Topic::Messages(room_id),
+        Topic::Members(room_id),
+    ]
+}
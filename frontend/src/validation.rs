@@ -0,0 +1,104 @@
+use crate::i18n;
+use validator::{ValidationError, ValidationErrors};
+
+// ========================// Validation //======================== //
+
+/// Message shown for an invalid field of a request, in the words of the
+/// locales rather than the English messages of the validators
+pub fn validation_error(errors: &ValidationErrors, field: &str, subject: &str) -> String {
+    let error = errors
+        .field_errors()
+        .get(field)
+        .and_then(|x| x.first().cloned());
+    let (key, min, max) = match error {
+        Some(error) => error_key(&error),
+        None => ("field-invalid", String::new(), String::new()),
+    };
+    i18n::tr(key, &[("subject", subject), ("min", &min), ("max", &max)])
+}
+
+// locale key of a failed validation, with its bounds if any
+fn error_key(error: &ValidationError) -> (&'static str, String, String) {
+    // the bounds of a range are floats, 4 is shown rather than 4.0
+    let param = |name: &str| {
+        let value = error.params.get(name)?;
+        Some(
+            value
+                .as_f64()
+                .map_or_else(|| value.to_string(), |x| x.to_string()),
+        )
+    };
+    let (min, max) = (param("min"), param("max"));
+    let key = match (error.code.as_ref(), &min, &max) {
+        ("length", Some(_), Some(_)) => "field-length-between",
+        ("length", Some(_), None) => "field-length-min",
+        ("length", None, Some(_)) => "field-length-max",
+        ("range", Some(_), Some(_)) => "field-range-between",
+        ("range", Some(_), None) => "field-range-min",
+        ("range", None, Some(_)) => "field-range-max",
+        _ => "field-invalid",
+    };
+    (key, min.unwrap_or_default(), max.unwrap_or_default())
+}
+
+/// Message shown for the first invalid field of a request, the fields given
+/// with the locale key of their name
+pub fn first_validation_error(errors: &ValidationErrors, fields: &[(&str, &str)]) -> String {
+    let field_errors = errors.field_errors();
+    let (field, subject) = fields
+        .iter()
+        .find(|(field, _)| field_errors.contains_key(field))
+        .unwrap_or(&fields[0]);
+    validation_error(errors, field, &i18n::t(subject))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{room::NewRoomNameResquest, user::ChangePasswordRequest};
+    use validator::Validate;
+
+    #[test]
+    fn validation_messages() {
+        let req = NewRoomNameResquest {
+            room_id: 0,
+            name: "x".to_owned(),
+        };
+        let errors = req.validate().unwrap_err();
+        assert_eq!(
+            validation_error(&errors, "name", "The name"),
+            "The name must be between 2 and 50 characters"
+        );
+        // the bounds of a range are written as integers
+        assert_eq!(
+            validation_error(&errors, "room_id", "The room"),
+            "The room must be at least 1"
+        );
+        assert_eq!(
+            validation_error(&errors, "cover", "The cover"),
+            "The cover is not valid"
+        );
+
+        let req = ChangePasswordRequest {
+            old_password: "secret".to_owned(),
+            new_password: "short".to_owned(),
+        };
+        let errors = req.validate().unwrap_err();
+        let fields = [
+            ("old_password", "password-current"),
+            ("new_password", "password-new"),
+        ];
+        let message = first_validation_error(&errors, &fields);
+        assert_eq!(
+            message,
+            i18n::tr(
+                "field-length-between",
+                &[
+                    ("subject", &i18n::t("password-new")),
+                    ("min", "6"),
+                    ("max", "50")
+                ]
+            )
+        );
+    }
+}
@@ -1,8 +1,20 @@
+// neutral colors are read from the palette of the theme, see input.css
+const themed = (name) => `rgb(var(--${name}) / <alpha-value>)`;
+const shades = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+
 /** @type {import('tailwindcss').Config} */
 module.exports = {
   content: ["./src/**/*.rs"],
   theme: {
-    extend: {},
+    extend: {
+      colors: {
+        slate: Object.fromEntries(shades.map((x) => [x, themed(`slate-${x}`)])),
+        // cards, menus and modals
+        surface: themed("surface"),
+        // the navigation bar, dark in every theme
+        nav: themed("nav"),
+      },
+    },
   },
   plugins: [],
 }
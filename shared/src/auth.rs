@@ -1,4 +1,5 @@
 use crate::user::UserInfo;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -53,12 +54,14 @@ pub struct LoginRequest {
 pub struct LoginResponse {
     pub user: UserInfo,
     pub access_token: String,
+    pub expire_at: DateTime<Utc>,
 }
 
 // ---------------- Renew token ---------------- //
 #[derive(Deserialize, Serialize)]
 pub struct RenewTokenResponse {
     pub access_token: String,
+    pub expire_at: DateTime<Utc>,
 }
 
 // ---------------- Logout ---------------- //
@@ -11,6 +11,8 @@ use crate::{
         NewRoomNameResponse, NewRoomNameResquest, NewRoomRequest, NewRoomResponse,
         UserRoomsResponse,
     },
+    session::{AuthenticateRequest, CloseReason, PingRequest, PongResponse, ResumeRequest},
+    user::UpdateUserResponse,
 };
 use serde::{Deserialize, Serialize};
 
@@ -19,8 +21,11 @@ use serde::{Deserialize, Serialize};
 /// Events from client to server
 #[derive(Deserialize, Serialize)]
 pub enum ClientEvent {
+    Authenticate(AuthenticateRequest),
     Close,
     Initialization,
+    Resume(ResumeRequest),
+    Ping(PingRequest),
     SendMessage(NewMessageRequest),
     // Room
     GetUserRooms,
@@ -44,7 +49,8 @@ pub enum ClientEvent {
 /// Events from server to client
 #[derive(Deserialize, Serialize)]
 pub enum ServerEvent {
-    Close(String),
+    Close(CloseReason),
+    Pong(PongResponse),
     Initialized(InitialResponse),
     ReceiveMessage(NewMessageResponse),
     // Room
@@ -62,4 +68,17 @@ pub enum ServerEvent {
     AcceptedFriend(AcceptFriendResponse),
     RefusedFriend(RefuseFriendResponse),
     DeletedFriend(DeleteFriendResponse),
+    // User
+    UserUpdated(UpdateUserResponse),
+}
+
+// ========================// ServerMessage //======================== //
+
+/// Frame from server to client
+#[derive(Deserialize, Serialize)]
+pub struct ServerMessage {
+    // per-user sequence number, increasing by one for every event that can be replayed,
+    // while `Initialized` carries the last one included in the snapshot
+    pub seq: Option<u64>,
+    pub event: ServerEvent,
 }
@@ -5,5 +5,6 @@ pub mod friend;
 pub mod member;
 pub mod message;
 pub mod room;
+pub mod session;
 pub mod user;
 pub mod validator;
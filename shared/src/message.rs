@@ -2,6 +2,7 @@ use super::validator as VAL;
 use crate::{friend::FriendInfo, room::RoomInfo};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use validator::Validate;
 
 // ========================// Message //======================== //
@@ -34,6 +35,8 @@ pub struct NewMessageRequest {
     pub content: String,
     #[validate(custom = "VAL::validate_message_kind")]
     pub kind: String,
+    // chosen by the client and echoed back, to match the message it displayed early
+    pub nonce: Option<Uuid>,
 }
 
 /// Used to pass a single message to client
@@ -41,4 +44,6 @@ pub struct NewMessageRequest {
 pub struct NewMessageResponse {
     pub room_id: i64,
     pub message: MessageInfo,
+    // nonce of the request, only sent back to its sender
+    pub nonce: Option<Uuid>,
 }
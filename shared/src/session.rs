@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// ========================// Session //======================== //
+
+// ---------------- Authenticate ---------------- //
+/// The first frame of every websocket session
+#[derive(Deserialize, Serialize)]
+pub struct AuthenticateRequest {
+    pub token: String,
+}
+
+// ---------------- Resume ---------------- //
+/// Sent instead of `Initialization` when reconnecting, the server replays the events
+/// after `last_seq`, or sends a full snapshot if too many of them have been missed
+#[derive(Deserialize, Serialize)]
+pub struct ResumeRequest {
+    pub last_seq: u64,
+}
+
+// ---------------- Heartbeat ---------------- //
+#[derive(Deserialize, Serialize)]
+pub struct PingRequest {
+    pub nonce: u64,
+}
+
+/// Answer to a ping, outside of the event sequence
+#[derive(Deserialize, Serialize)]
+pub struct PongResponse {
+    pub nonce: u64,
+    pub server_time: DateTime<Utc>,
+}
+
+// ---------------- Close ---------------- //
+/// Why the server closed the websocket session
+#[derive(Deserialize, Serialize)]
+pub enum CloseReason {
+    // the token is missing or invalid
+    Unauthorized,
+    // the token has expired
+    Expired,
+    // any other error, which is not recoverable
+    Error(String),
+}
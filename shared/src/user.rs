@@ -42,6 +42,8 @@ pub struct CreateUserResponse {
 }
 
 // ---------------- Delete users ---------------- //
+/// Soft delete: the users are only marked as deleted, and `UpdateUserRequest`
+/// with `deleted: Some(false)` restores them.
 #[derive(Deserialize, Serialize, Validate)]
 pub struct DeleteUsersRequest {
     #[validate(custom = "VAL::validate_id_vec")]
@@ -54,6 +56,8 @@ pub struct DeleteUsersResponse {
 }
 
 // ---------------- Update user ---------------- //
+/// Fields left as `None` are kept as they are. `avatar` and `bio` may be
+/// `Some("")`, which clears them; the other fields can't be emptied.
 #[derive(Deserialize, Serialize, Validate)]
 pub struct UpdateUserRequest {
     #[validate(range(min = 1, message = "user id is invalid"))]
@@ -76,17 +80,11 @@ pub struct UpdateUserRequest {
         message = "must be between 2 and 50 characters"
     ))]
     pub nickname: Option<String>,
-    #[validate(length(
-        min = 1,
-        max = 200,
-        message = "must be between 1 and 200 characters"
-    ))]
+    /// an empty string clears the avatar
+    #[validate(length(max = 200, message = "must be at most 200 characters"))]
     pub avatar: Option<String>,
-    #[validate(length(
-        min = 1,
-        max = 200,
-        message = "must be between 1 and 200 characters"
-    ))]
+    /// an empty string clears the bio
+    #[validate(length(max = 200, message = "must be at most 200 characters"))]
     pub bio: Option<String>,
     #[validate(custom = "VAL::validate_user_role")]
     pub role: Option<String>,
@@ -98,6 +96,28 @@ pub struct UpdateUserResponse {
     pub user: UserInfo,
 }
 
+// ---------------- Change password ---------------- //
+#[derive(Deserialize, Serialize, Validate)]
+pub struct ChangePasswordRequest {
+    #[validate(length(
+        min = 6,
+        max = 50,
+        message = "must be between 6 and 50 characters"
+    ))]
+    pub old_password: String,
+    #[validate(length(
+        min = 6,
+        max = 50,
+        message = "must be between 6 and 50 characters"
+    ))]
+    pub new_password: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ChangePasswordResponse {
+    pub message: String,
+}
+
 // ---------------- List users ---------------- //
 #[derive(Deserialize, Serialize, Validate)]
 pub struct ListUsersRequest {
@@ -38,7 +38,7 @@ pub fn validate_room_rank(rank: &str) -> Result<(), ValidationError> {
 }
 
 pub fn validate_message_kind(kind: &str) -> Result<(), ValidationError> {
-    let kinds = vec!["text", "img"];
+    let kinds = vec!["text", "img", "action"];
     oneof(kind, &kinds)
 }
 
@@ -47,7 +47,7 @@ pub fn validate_id_vec(ids: &Vec<i64>) -> Result<(), ValidationError> {
     for &id in ids {
         if id < 1 || !seen.insert(id) {
             let mut e = ValidationError::new("vec");
-            let msg = format!("must be greater than 0 and not contain duplicate numbers");
+            let msg = "must be greater than 0 and not contain duplicate numbers".to_string();
             e.message = Some(Cow::from(msg));
             return Err(e);
         }